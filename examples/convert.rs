@@ -2,14 +2,14 @@
 /// Reference link : https://github.com/rust-lang-nursery/simd
 
 extern crate ssimd;
-use ssimd::f32x4;
+use ssimd::{f32x4, i32x4};
 
 #[inline(never)]
 pub fn convert_scalar(x: &mut [i32], y: &[f32]) {
     assert_eq!(x.len(), y.len());
 
     let mut i = 0;
-    while i < x.len() & !3 {
+    while i < x.len() {
         x[i] = y[i] as i32;
         i += 1;
     }
@@ -19,11 +19,8 @@ pub fn convert_scalar(x: &mut [i32], y: &[f32]) {
 pub fn convert(x: &mut [i32], y: &[f32]) {
     assert_eq!(x.len(), y.len());
 
-    let mut i = 0;
-    while i < x.len() & !3 {
-        let v = f32x4::load(y, i);
-        v.to_i().store(x, i);
-        i += 4
+    for (v, mut out) in f32x4::chunks(y).zip(i32x4::chunks_mut(x)) {
+        out.store(v.to_i());
     }
 }
 
@@ -38,4 +35,8 @@ fn main() {
     convert(x, &y);
     convert_scalar(x, &y);
     println!("{:?}", x);
+    let x = &mut [0; 7];
+    let y = [2.5; 7];
+    convert(x, &y);
+    println!("{:?}", x);
 }
@@ -40,8 +40,8 @@ fn mandelbrot_vector(c_x: f32x4, c_y: f32x4, max_iter: u32) -> u32x4 {
         let mask = sum.lt(f32x4::splat(4.0));
 
         if !mask.any() { break }
-        count = count + mask.to_u().select(u32x4::splat(1),
-                                           u32x4::splat(0));
+        count += mask.to_u().select(u32x4::splat(1),
+                                    u32x4::splat(0));
 
         x = xx - yy + c_x;
         y = xy + xy + c_y;
@@ -95,7 +95,7 @@ fn main() {
     let width_step: f32 = (right - left) / width as f32;
     let height_step: f32 = (bottom - top) / height as f32;
 
-    let adjust = f32x4::splat(width_step) * f32x4::new(0., 1., 2., 3.);
+    let adjust = width_step * f32x4::new(0., 1., 2., 3.);
 
     println!("P6 {} {} 255", width, height);
     let mut line = vec![0; width * 3];
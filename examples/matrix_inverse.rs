@@ -1,42 +1,39 @@
 /// Slightly modify the example on crate `simd` to make it work on stable channel 
 /// Reference link : https://github.com/rust-lang-nursery/simd
 
+#[macro_use]
 extern crate ssimd;
-use ssimd::f32x4;
+use ssimd::{f32x4, transpose4};
 
 fn mul(x: &[f32x4; 4], y: &[f32x4; 4]) -> [f32x4; 4] {
     let y0 = y[0];
     let y1 = y[1];
     let y2 = y[2];
     let y3 = y[3];
-    [f32x4::splat(y0.extract(0)) * x[0] +
-     f32x4::splat(y0.extract(1)) * x[1] +
-     f32x4::splat(y0.extract(2)) * x[2] +
-     f32x4::splat(y0.extract(3)) * x[3],
-     f32x4::splat(y1.extract(0)) * x[0] +
-     f32x4::splat(y1.extract(1)) * x[1] +
-     f32x4::splat(y1.extract(2)) * x[2] +
-     f32x4::splat(y1.extract(3)) * x[3],
-     f32x4::splat(y2.extract(0)) * x[0] +
-     f32x4::splat(y2.extract(1)) * x[1] +
-     f32x4::splat(y2.extract(2)) * x[2] +
-     f32x4::splat(y2.extract(3)) * x[3],
-     f32x4::splat(y3.extract(0)) * x[0] +
-     f32x4::splat(y3.extract(1)) * x[1] +
-     f32x4::splat(y3.extract(2)) * x[2] +
-     f32x4::splat(y3.extract(3)) * x[3],
+    [swizzle!(y0, [0, 0, 0, 0]) * x[0] +
+     swizzle!(y0, [1, 1, 1, 1]) * x[1] +
+     swizzle!(y0, [2, 2, 2, 2]) * x[2] +
+     swizzle!(y0, [3, 3, 3, 3]) * x[3],
+     swizzle!(y1, [0, 0, 0, 0]) * x[0] +
+     swizzle!(y1, [1, 1, 1, 1]) * x[1] +
+     swizzle!(y1, [2, 2, 2, 2]) * x[2] +
+     swizzle!(y1, [3, 3, 3, 3]) * x[3],
+     swizzle!(y2, [0, 0, 0, 0]) * x[0] +
+     swizzle!(y2, [1, 1, 1, 1]) * x[1] +
+     swizzle!(y2, [2, 2, 2, 2]) * x[2] +
+     swizzle!(y2, [3, 3, 3, 3]) * x[3],
+     swizzle!(y3, [0, 0, 0, 0]) * x[0] +
+     swizzle!(y3, [1, 1, 1, 1]) * x[1] +
+     swizzle!(y3, [2, 2, 2, 2]) * x[2] +
+     swizzle!(y3, [3, 3, 3, 3]) * x[3],
      ]
 }
 
 #[allow(dead_code)]
 fn inverse_naive(x: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
-    let mut t = [[0_f32; 4]; 4];
-    for i in 0..4 {
-        t[0][i] = x[i][0];
-        t[1][i] = x[i][1];
-        t[2][i] = x[i][2];
-        t[3][i] = x[i][3];
-    }
+    let mut rows = x.map(f32x4::from);
+    transpose4(&mut rows);
+    let t = rows.map(|r| r.to_array());
     println!("{:?}", t);
 
     let _0 = t[2][2] * t[3][3];
@@ -128,78 +125,57 @@ fn inverse_simd4(x: &[f32x4; 4]) -> [f32x4; 4] {
     let src2 = x[2];
     let src3 = x[3];
 
-    let tmp1 = f32x4::new(src0.extract(0), src0.extract(1),
-                          src1.extract(4 - 4), src1.extract(5 - 4));
-    let row1 = f32x4::new(src2.extract(0), src2.extract(1),
-                          src3.extract(4 - 4), src3.extract(5 - 4));
-    let row0 = f32x4::new(tmp1.extract(0), tmp1.extract(2),
-                          row1.extract(4 - 4), row1.extract(6 - 4));
-    let row1 = f32x4::new(row1.extract(1), row1.extract(3),
-                          tmp1.extract(5 - 4), tmp1.extract(7 - 4));
-
-    let tmp1 = f32x4::new(src0.extract(2), src0.extract(3),
-                          src1.extract(6 - 4), src1.extract(7 - 4));
-    let row3 = f32x4::new(src2.extract(2), src2.extract(3),
-                          src3.extract(6 - 4), src3.extract(7 - 4));
-    let row2 = f32x4::new(tmp1.extract(0), tmp1.extract(2),
-                          row3.extract(4 - 4), row3.extract(6 - 4));
-    let row3 = f32x4::new(row3.extract(1), row3.extract(3),
-                          tmp1.extract(5 - 4), tmp1.extract(7 - 4));
+    let tmp1 = shuffle!(src0, src1, [0, 1, 4, 5]);
+    let row1 = shuffle!(src2, src3, [0, 1, 4, 5]);
+    let row0 = shuffle!(tmp1, row1, [0, 2, 4, 6]);
+    let row1 = shuffle!(row1, tmp1, [1, 3, 5, 7]);
+
+    let tmp1 = shuffle!(src0, src1, [2, 3, 6, 7]);
+    let row3 = shuffle!(src2, src3, [2, 3, 6, 7]);
+    let row2 = shuffle!(tmp1, row3, [0, 2, 4, 6]);
+    let row3 = shuffle!(row3, tmp1, [1, 3, 5, 7]);
 
 
     let tmp1 = row2 * row3;
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
     let minor0 = row1 * tmp1;
     let minor1 = row0 * tmp1;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
     let minor0 = (row1 * tmp1) - minor0;
     let minor1 = (row0 * tmp1) - minor1;
-    let minor1 = f32x4::new(minor1.extract(2), minor1.extract(3),
-                            minor1.extract(0), minor1.extract(1));
+    let minor1 = swizzle!(minor1, [2, 3, 0, 1]);
     //println!("{:?}", minor1);
 
 
     let tmp1 = row1 * row2;
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
     let minor0 = (row3 * tmp1) + minor0;
     let minor3 = row0 * tmp1;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
 
     let minor0 = minor0 - row3 * tmp1;
     let minor3 = row0 * tmp1 - minor3;
-    let minor3 = f32x4::new(minor3.extract(2), minor3.extract(3),
-                            minor3.extract(0), minor3.extract(1));
+    let minor3 = swizzle!(minor3, [2, 3, 0, 1]);
     //println!("{:?}", minor1);
 
 
-    let tmp1 = row3 * f32x4::new(row1.extract(2), row1.extract(3),
-                                 row1.extract(0), row1.extract(1));
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
-    let row2 = f32x4::new(row2.extract(2), row2.extract(3),
-                          row2.extract(0), row2.extract(1));
+    let tmp1 = row3 * swizzle!(row1, [2, 3, 0, 1]);
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
+    let row2 = swizzle!(row2, [2, 3, 0, 1]);
     let minor0 = row2 * tmp1 + minor0;
     let minor2 = row0 * tmp1;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
     let minor0 = minor0 - row2 * tmp1;
     let minor2 = row0 * tmp1 - minor2;
-    let minor2 = f32x4::new(minor2.extract(2), minor2.extract(3),
-                            minor2.extract(0), minor2.extract(1));
+    let minor2 = swizzle!(minor2, [2, 3, 0, 1]);
     //println!("{:?}", minor1);
 
 
     let tmp1 = row0 * row1;
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
     let minor2 = minor2 + row3 * tmp1;
     let minor3 = row2 * tmp1 - minor3;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
     let minor2 = row3 * tmp1 - minor2;
     let minor3 = minor3 - row2 * tmp1;
     //println!("{:?}", minor1);
@@ -207,35 +183,29 @@ fn inverse_simd4(x: &[f32x4; 4]) -> [f32x4; 4] {
 
 
     let tmp1 = row0 * row3;
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
     let minor1 = minor1 - row2 * tmp1;
     let minor2 = row1 * tmp1 + minor2;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
     let minor1 = row2 * tmp1 + minor1;
     let minor2 = minor2 - row1 * tmp1;
     //println!("{:?}", minor1);
 
     let tmp1 = row0 * row2;
-    let tmp1 = f32x4::new(tmp1.extract(1), tmp1.extract(0),
-                          tmp1.extract(3), tmp1.extract(2));
+    let tmp1 = swizzle!(tmp1, [1, 0, 3, 2]);
     let minor1 = row3 * tmp1 + minor1;
     let minor3 = minor3 - row1 * tmp1;
-    let tmp1 = f32x4::new(tmp1.extract(2), tmp1.extract(3),
-                          tmp1.extract(0), tmp1.extract(1));
+    let tmp1 = swizzle!(tmp1, [2, 3, 0, 1]);
     let minor1 = minor1 - row3 * tmp1;
     let minor3 = row1 * tmp1 + minor3;
     //println!("{:?}", minor1);
 
     let det = row0 * minor0;
-    let det = f32x4::new(det.extract(2), det.extract(3),
-                         det.extract(0), det.extract(1)) + det;
-    let det = f32x4::new(det.extract(1), det.extract(0),
-                         det.extract(3), det.extract(2)) + det;
+    let det = swizzle!(det, [2, 3, 0, 1]) + det;
+    let det = swizzle!(det, [1, 0, 3, 2]) + det;
     //let tmp1 = det.approx_reciprocal();
     //let det = tmp1 + tmp1 - det * tmp1 * tmp1;
-    let det = f32x4::splat(1.0) / det;
+    let det = 1.0 / det;
 
 //    let det = f32x4::splat(det.extract(0));
 
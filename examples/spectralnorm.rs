@@ -23,10 +23,10 @@ fn mult_Av(v: &[f64], out: &mut [f64]) {
         while j < v.len() {
             let b = f64x2::load(v, j);
             let a = f64x2::new(A(i, j), A(i, j + 1));
-            sum = sum + b / a;
+            sum += b / a;
             j += 2
         }
-        out[i] = sum.extract(0) + sum.extract(1);
+        out[i] = sum.sum();
     }
 }
 
@@ -41,10 +41,10 @@ fn mult_Atv(v: &[f64], out: &mut [f64]) {
         while j < v.len() {
             let b = f64x2::load(v, j);
             let a = f64x2::new(A(j, i), A(j + 1, i));
-            sum = sum + b / a;
+            sum += b / a;
             j += 2
         }
-        out[i] = sum.extract(0) + sum.extract(1);
+        out[i] = sum.sum();
     }
 }
 
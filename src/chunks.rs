@@ -0,0 +1,169 @@
+// Iterators that walk slices one vector at a time. The last vector of a slice
+// whose length is not a multiple of the lane count is padded, and stores to it
+// drop the padding lanes, so kernels need no scalar loop for the tail.
+
+use std::marker::PhantomData;
+use std::mem;
+
+use {Simd, LaneCount, SupportedLaneCount, SimdElement};
+
+/// Iterator over a slice as vectors of `N` lanes, returned by `Simd::chunks`.
+/// The last vector is padded if the slice length is not a multiple of `N`.
+pub struct SimdChunks<'a, T: 'a, const N: usize> {
+    slice: &'a [T],
+    fill: T,
+}
+
+impl<'a, T: SimdElement, const N: usize> Iterator for SimdChunks<'a, T, N> where LaneCount<N>: SupportedLaneCount {
+    type Item = Simd<T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Simd<T, N>> {
+        if self.slice.is_empty() {
+            return None;
+        }
+        let n = self.slice.len().min(N);
+        let v = if n == N {
+            Simd::load(self.slice, 0)
+        } else {
+            Simd::load_partial(self.slice, 0, self.fill)
+        };
+        self.slice = &self.slice[n..];
+        Some(v)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.slice.len().div_ceil(N);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: SimdElement, const N: usize> ExactSizeIterator for SimdChunks<'a, T, N>
+    where LaneCount<N>: SupportedLaneCount {}
+
+/// Iterator over a mutable slice as chunks of `N` lanes, returned by
+/// `Simd::chunks_mut`
+pub struct SimdChunksMut<'a, T: 'a, const N: usize> {
+    slice: &'a mut [T],
+    fill: T,
+}
+
+impl<'a, T: SimdElement, const N: usize> Iterator for SimdChunksMut<'a, T, N> where LaneCount<N>: SupportedLaneCount {
+    type Item = SimdChunkMut<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<SimdChunkMut<'a, T, N>> {
+        let slice = mem::take(&mut self.slice);
+        if slice.is_empty() {
+            return None;
+        }
+        let n = slice.len().min(N);
+        let (chunk, rest) = slice.split_at_mut(n);
+        self.slice = rest;
+        Some(SimdChunkMut { slice: chunk, fill: self.fill, lanes: PhantomData })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.slice.len().div_ceil(N);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: SimdElement, const N: usize> ExactSizeIterator for SimdChunksMut<'a, T, N>
+    where LaneCount<N>: SupportedLaneCount {}
+
+/// Up to `N` elements of a mutable slice, read and written as one vector
+pub struct SimdChunkMut<'a, T: 'a, const N: usize> {
+    slice: &'a mut [T],
+    fill: T,
+    lanes: PhantomData<[T; N]>,
+}
+
+impl<'a, T: SimdElement, const N: usize> SimdChunkMut<'a, T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Load the chunk, padding the lanes past the end of the slice
+    #[inline]
+    pub fn load(&self) -> Simd<T, N> {
+        Simd::load_partial(self.slice, 0, self.fill)
+    }
+
+    /// Store a vector to the chunk, dropping the lanes past the end of the slice
+    #[inline]
+    pub fn store(&mut self, v: Simd<T, N>) {
+        v.store_partial(self.slice, 0)
+    }
+
+    /// Get the elements of the chunk, `N` of them except for the last chunk
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.slice
+    }
+}
+
+impl<T: SimdElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Iterate over a slice as vectors. The last vector is padded with
+    /// zeros if the slice length is not a multiple of `N`.
+    #[inline]
+    pub fn chunks(slice: &[T]) -> SimdChunks<'_, T, N> {
+        Self::chunks_padded(slice, T::default())
+    }
+
+    /// Iterate over a slice as vectors. The last vector is padded with
+    /// `fill` if the slice length is not a multiple of `N`.
+    #[inline]
+    pub fn chunks_padded(slice: &[T], fill: T) -> SimdChunks<'_, T, N> {
+        SimdChunks { slice, fill }
+    }
+
+    /// Iterate over a mutable slice in chunks of `N` elements, each loaded
+    /// and stored as a vector. The last chunk is padded with zeros when
+    /// loaded, and the padding lanes are dropped when stored.
+    #[inline]
+    pub fn chunks_mut(slice: &mut [T]) -> SimdChunksMut<'_, T, N> {
+        SimdChunksMut { slice, fill: T::default() }
+    }
+
+    /// Iterate over two slices of the same length as pairs of vectors,
+    /// padded with zeros like `chunks`.
+    /// Panics if the lengths differ.
+    #[inline]
+    pub fn chunks_zip<'a>(a: &'a [T], b: &'a [T]) -> impl ExactSizeIterator<Item = (Self, Self)> + 'a {
+        assert_eq!(a.len(), b.len());
+        Self::chunks(a).zip(Self::chunks(b))
+    }
+
+    /// Iterate over three slices of the same length as triples of vectors,
+    /// padded with zeros like `chunks`.
+    /// Panics if the lengths differ.
+    #[inline]
+    pub fn chunks_zip3<'a>(a: &'a [T], b: &'a [T], c: &'a [T])
+                           -> impl ExactSizeIterator<Item = (Self, Self, Self)> + 'a {
+        assert_eq!(a.len(), b.len());
+        assert_eq!(a.len(), c.len());
+        Self::chunks(a).zip(Self::chunks(b)).zip(Self::chunks(c)).map(|((a, b), c)| (a, b, c))
+    }
+
+    /// Set `dst` to `f` applied to `src`, one vector at a time, including
+    /// the tail. The padding lanes of the last vector are zeros, and their
+    /// results are dropped.
+    /// Panics if the lengths differ.
+    #[inline]
+    pub fn map_slice(src: &[T], dst: &mut [T], mut f: impl FnMut(Self) -> Self) {
+        assert_eq!(src.len(), dst.len());
+        for (x, mut out) in Self::chunks(src).zip(Self::chunks_mut(dst)) {
+            out.store(f(x));
+        }
+    }
+
+    /// Set `dst` to `f` applied to `a` and `b`, one pair of vectors at a
+    /// time, like `map_slice`.
+    /// Panics if the lengths differ.
+    #[inline]
+    pub fn zip_map(a: &[T], b: &[T], dst: &mut [T], mut f: impl FnMut(Self, Self) -> Self) {
+        assert_eq!(a.len(), dst.len());
+        for ((x, y), mut out) in Self::chunks_zip(a, b).zip(Self::chunks_mut(dst)) {
+            out.store(f(x, y));
+        }
+    }
+}
@@ -0,0 +1,316 @@
+// Lane element types and lane counts supported by `Simd` and `Mask`.
+//
+// The traits are sealed. Their hidden methods are the per-lane operations the
+// generic impls in lib.rs are written with: integer arithmetic wraps, and shifts
+// by the lane width or more shift every bit out instead of panicking.
+
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, BitAnd, BitOr, BitXor, Not};
+
+mod sealed {
+    pub trait Sealed {}
+}
+pub(crate) use self::sealed::Sealed;
+
+/// Zero-sized fields that align a vector to its size, capped at 64 bytes
+#[doc(hidden)]
+pub mod align {
+    #[repr(align(2))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A2;
+    #[repr(align(4))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A4;
+    #[repr(align(8))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A8;
+    #[repr(align(16))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A16;
+    #[repr(align(32))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A32;
+    #[repr(align(64))]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct A64;
+}
+
+/// Number of lanes of a vector.
+/// `LaneCount<N>: SupportedLaneCount` holds for N = 2, 4, 8, 16, 32 and 64.
+pub struct LaneCount<const N: usize>;
+
+/// Lane counts that `Simd` and `Mask` can be built with
+pub trait SupportedLaneCount: Sealed {
+    #[doc(hidden)]
+    type Align1: Copy + Default;
+    #[doc(hidden)]
+    type Align2: Copy + Default;
+    #[doc(hidden)]
+    type Align4: Copy + Default;
+    #[doc(hidden)]
+    type Align8: Copy + Default;
+}
+
+macro_rules! lane_count_impls {
+    ($($lanes: expr => $a1: ident, $a2: ident, $a4: ident, $a8: ident;)*) => {
+        $(impl Sealed for LaneCount<$lanes> {}
+
+        impl SupportedLaneCount for LaneCount<$lanes> {
+            type Align1 = align::$a1;
+            type Align2 = align::$a2;
+            type Align4 = align::$a4;
+            type Align8 = align::$a8;
+        })*
+    }
+}
+
+lane_count_impls! {
+    2 => A2, A4, A8, A16;
+    4 => A4, A8, A16, A32;
+    8 => A8, A16, A32, A64;
+    16 => A16, A32, A64, A64;
+    32 => A32, A64, A64, A64;
+    64 => A64, A64, A64, A64;
+}
+
+/// Types that can be stored in the lanes of a `Simd`
+pub trait SimdElement: Copy + Debug + Default + PartialEq + PartialOrd + Div<Output = Self> + Sealed {
+    /// Lane type of the boolean vector returned by comparisons,
+    /// the signed integer of the same width
+    type Mask: MaskElement;
+
+    #[doc(hidden)]
+    type Align<L: SupportedLaneCount>: Copy + Default;
+    #[doc(hidden)]
+    const NAME: &'static str;
+
+    #[doc(hidden)]
+    fn lane_add(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_sub(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_mul(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_neg(self) -> Self;
+}
+
+/// Integer types that can be stored in the lanes of a `Simd`
+pub trait IntElement: SimdElement + Eq + Ord + Rem<Output = Self>
+    + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> + Not<Output = Self> {
+    #[doc(hidden)]
+    fn lane_saturating_add(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_saturating_sub(self, rhs: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_shl(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn lane_shr(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn lane_wrapping_shl(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn lane_wrapping_shr(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn lane_rotate_left(self, n: u32) -> Self;
+    #[doc(hidden)]
+    fn lane_rotate_right(self, n: u32) -> Self;
+    /// The lane read as an unsigned shift count, saturated to `u32`
+    #[doc(hidden)]
+    fn lane_shift_count(self) -> u32;
+    /// The lane read as a rotate count, modulo `2^32`
+    #[doc(hidden)]
+    fn lane_rotate_count(self) -> u32;
+}
+
+/// Float types that can be stored in the lanes of a `Simd`
+pub trait FloatElement: SimdElement + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Neg<Output = Self> {
+    #[doc(hidden)]
+    fn lane_sqrt(self) -> Self;
+    #[doc(hidden)]
+    fn lane_recip(self) -> Self;
+    #[doc(hidden)]
+    fn lane_mul_add(self, a: Self, b: Self) -> Self;
+    #[doc(hidden)]
+    fn lane_floor(self) -> Self;
+    #[doc(hidden)]
+    fn lane_ceil(self) -> Self;
+    #[doc(hidden)]
+    fn lane_round(self) -> Self;
+    #[doc(hidden)]
+    fn lane_round_ties_even(self) -> Self;
+    #[doc(hidden)]
+    fn lane_trunc(self) -> Self;
+    #[doc(hidden)]
+    fn lane_fract(self) -> Self;
+}
+
+/// Types that can be stored in the lanes of a `Mask`: a true lane is all ones
+/// and a false lane is all zeros
+pub trait MaskElement: IntElement {
+    /// Unsigned integer of the same width, the lane type of `Mask::to_u`
+    type Unsigned: IntElement;
+
+    #[doc(hidden)]
+    const MASK_NAME: &'static str;
+
+    #[doc(hidden)]
+    fn from_bool(b: bool) -> Self;
+    #[doc(hidden)]
+    fn to_bool(self) -> bool;
+    #[doc(hidden)]
+    fn to_unsigned(self) -> Self::Unsigned;
+}
+
+/// Integer types that can be stored in the lanes of an index vector, for
+/// `gather` and `scatter`
+pub trait IndexElement: IntElement {
+    /// The lane as a slice index. Negative lanes, and lanes that do not fit
+    /// in `usize`, give `usize::MAX`, which is out of bounds for any slice.
+    #[doc(hidden)]
+    fn lane_index(self) -> usize;
+}
+
+macro_rules! int_element_impls {
+    ($($elem: ident, $mask: ident, $align: ident;)*) => {
+        $(impl Sealed for $elem {}
+
+        impl SimdElement for $elem {
+            type Mask = $mask;
+            type Align<L: SupportedLaneCount> = L::$align;
+            const NAME: &'static str = stringify!($elem);
+
+            #[inline(always)]
+            fn lane_add(self, rhs: Self) -> Self { self.wrapping_add(rhs) }
+            #[inline(always)]
+            fn lane_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+            #[inline(always)]
+            fn lane_mul(self, rhs: Self) -> Self { self.wrapping_mul(rhs) }
+            #[inline(always)]
+            fn lane_neg(self) -> Self { self.wrapping_neg() }
+        }
+
+        impl IntElement for $elem {
+            #[inline(always)]
+            fn lane_saturating_add(self, rhs: Self) -> Self { self.saturating_add(rhs) }
+            #[inline(always)]
+            fn lane_saturating_sub(self, rhs: Self) -> Self { self.saturating_sub(rhs) }
+            #[inline(always)]
+            fn lane_shl(self, n: u32) -> Self {
+                self.checked_shl(n).unwrap_or(0)
+            }
+            #[inline(always)]
+            fn lane_shr(self, n: u32) -> Self {
+                self.checked_shr(n).unwrap_or((self >> ($elem::BITS - 1)) >> 1)
+            }
+            #[inline(always)]
+            fn lane_wrapping_shl(self, n: u32) -> Self { self.wrapping_shl(n) }
+            #[inline(always)]
+            fn lane_wrapping_shr(self, n: u32) -> Self { self.wrapping_shr(n) }
+            #[inline(always)]
+            fn lane_rotate_left(self, n: u32) -> Self { self.rotate_left(n) }
+            #[inline(always)]
+            fn lane_rotate_right(self, n: u32) -> Self { self.rotate_right(n) }
+            #[inline(always)]
+            fn lane_shift_count(self) -> u32 {
+                (self as u64).min(u32::MAX as u64) as u32
+            }
+            #[inline(always)]
+            fn lane_rotate_count(self) -> u32 { self as u32 }
+        })*
+    }
+}
+
+int_element_impls! {
+    u8, i8, Align1;
+    i8, i8, Align1;
+    u16, i16, Align2;
+    i16, i16, Align2;
+    u32, i32, Align4;
+    i32, i32, Align4;
+    u64, i64, Align8;
+    i64, i64, Align8;
+}
+
+macro_rules! float_element_impls {
+    ($($elem: ident, $mask: ident, $align: ident;)*) => {
+        $(impl Sealed for $elem {}
+
+        impl SimdElement for $elem {
+            type Mask = $mask;
+            type Align<L: SupportedLaneCount> = L::$align;
+            const NAME: &'static str = stringify!($elem);
+
+            #[inline(always)]
+            fn lane_add(self, rhs: Self) -> Self { self + rhs }
+            #[inline(always)]
+            fn lane_sub(self, rhs: Self) -> Self { self - rhs }
+            #[inline(always)]
+            fn lane_mul(self, rhs: Self) -> Self { self * rhs }
+            #[inline(always)]
+            fn lane_neg(self) -> Self { -self }
+        }
+
+        impl FloatElement for $elem {
+            #[inline(always)]
+            fn lane_sqrt(self) -> Self { self.sqrt() }
+            #[inline(always)]
+            fn lane_recip(self) -> Self { 1.0 / self }
+            #[inline(always)]
+            fn lane_mul_add(self, a: Self, b: Self) -> Self { self.mul_add(a, b) }
+            #[inline(always)]
+            fn lane_floor(self) -> Self { self.floor() }
+            #[inline(always)]
+            fn lane_ceil(self) -> Self { self.ceil() }
+            #[inline(always)]
+            fn lane_round(self) -> Self { self.round() }
+            #[inline(always)]
+            fn lane_round_ties_even(self) -> Self { self.round_ties_even() }
+            #[inline(always)]
+            fn lane_trunc(self) -> Self { self.trunc() }
+            #[inline(always)]
+            fn lane_fract(self) -> Self { self.fract() }
+        })*
+    }
+}
+
+float_element_impls! {
+    f32, i32, Align4;
+    f64, i64, Align8;
+}
+
+macro_rules! mask_element_impls {
+    ($($elem: ident, $unsigned: ident, $name: expr;)*) => {
+        $(impl MaskElement for $elem {
+            type Unsigned = $unsigned;
+            const MASK_NAME: &'static str = $name;
+
+            #[inline(always)]
+            fn from_bool(b: bool) -> Self { -(b as $elem) }
+            #[inline(always)]
+            fn to_bool(self) -> bool { self != 0 }
+            #[inline(always)]
+            fn to_unsigned(self) -> $unsigned { self as $unsigned }
+        })*
+    }
+}
+
+mask_element_impls! {
+    i8, u8, "bool8";
+    i16, u16, "bool16";
+    i32, u32, "bool32";
+    i64, u64, "bool64";
+}
+
+macro_rules! index_element_impls {
+    ($($elem: ident),*) => {
+        $(impl IndexElement for $elem {
+            #[inline(always)]
+            fn lane_index(self) -> usize {
+                usize::try_from(self).unwrap_or(usize::MAX)
+            }
+        })*
+    }
+}
+
+index_element_impls! { u32, i32, u64 }
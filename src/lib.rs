@@ -2,676 +2,1361 @@
 // Inspired by crate `simd` developed for nightly Channel
 // Reference link : https://github.com/rust-lang-nursery/simd
 
-#![allow(non_camel_case_types)]
-use std::ops::{Add, Sub, Mul, Div, BitAnd, BitOr, BitXor, Not, Shl, Shr};
+#![allow(non_camel_case_types, unused_doc_comments)]
+use std::array;
+use std::fmt;
+use std::slice;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, ShlAssign, ShrAssign};
+use std::ops::{Index, IndexMut};
 
-/// 2x32-bit vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u32x2(u32, u32);
+mod chunks;
+mod element;
+mod math;
+mod swizzle;
+mod traits;
+mod transpose;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i32x2(i32, i32);
+pub use element::{LaneCount, SupportedLaneCount, SimdElement, IntElement, FloatElement, MaskElement, IndexElement};
+use element::Sealed;
+pub use traits::{SimdVector, SimdFloat, SimdInt, SimdMask};
+pub use chunks::{SimdChunks, SimdChunksMut, SimdChunkMut};
+pub use swizzle::Shuffle;
+pub use transpose::{transpose4, transpose4_f64, transpose8};
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct f32x2(f32, f32);
+/// A vector of `N` lanes of type `T`, stored in an array that is aligned to the
+/// size of the vector (at most 64 bytes).
+/// `f32x4`, `u8x16` and the other concrete vector types are aliases of `Simd`.
+#[repr(C)]
+pub struct Simd<T: SimdElement, const N: usize>([T; N], T::Align<LaneCount<N>>)
+    where LaneCount<N>: SupportedLaneCount;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool32x2(i32, i32);
+/// A boolean vector of `N` lanes, where each lane of type `T` is all ones (true)
+/// or all zeros (false). It is returned by the comparisons of `Simd<U, N>` where
+/// `U::Mask` is `T`.
+/// `bool32x4` and the other concrete boolean vector types are aliases of `Mask`.
+#[repr(C)]
+pub struct Mask<T: MaskElement, const N: usize>([T; N], T::Align<LaneCount<N>>)
+    where LaneCount<N>: SupportedLaneCount;
 
-/// 4x32-bit vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u32x4(u32, u32, u32, u32);
+impl<T: SimdElement, const N: usize> Clone for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i32x4(i32, i32, i32, i32);
+impl<T: SimdElement, const N: usize> Copy for Simd<T, N> where LaneCount<N>: SupportedLaneCount {}
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct f32x4(f32, f32, f32, f32);
+impl<T: MaskElement, const N: usize> Clone for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool32x4(i32, i32, i32, i32);
+impl<T: MaskElement, const N: usize> Copy for Mask<T, N> where LaneCount<N>: SupportedLaneCount {}
 
-/// 16x8-bit integer vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u16x8(u16, u16, u16, u16,
-                 u16, u16, u16, u16);
-
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i16x8(i16, i16, i16, i16,
-                 i16, i16, i16, i16);
-                 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool16x8(i16, i16, i16, i16,
-                     i16, i16, i16, i16);
+/// Formats as the concrete type name, e.g. `f32x4([1.0, 2.0, 3.0, 4.0])`
+impl<T: SimdElement, const N: usize> fmt::Debug for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple(&format!("{}x{}", T::NAME, N)).field(&self.0).finish()
+    }
+}
+
+/// Formats as the concrete type name, e.g. `bool32x2([-1, 0])`
+impl<T: MaskElement, const N: usize> fmt::Debug for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple(&format!("{}x{}", T::MASK_NAME, N)).field(&self.0).finish()
+    }
+}
+
+/// 2x32-bit vectors
+pub type u32x2 = Simd<u32, 2>;
+pub type i32x2 = Simd<i32, 2>;
+pub type f32x2 = Simd<f32, 2>;
+pub type bool32x2 = Mask<i32, 2>;
+
+/// 4x32-bit vectors
+pub type u32x4 = Simd<u32, 4>;
+pub type i32x4 = Simd<i32, 4>;
+pub type f32x4 = Simd<f32, 4>;
+pub type bool32x4 = Mask<i32, 4>;
 
 /// 8x16-bit integer vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u8x16(u8, u8, u8, u8, u8, u8, u8, u8,
-                 u8, u8, u8, u8, u8, u8, u8, u8);
-
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i8x16(i8, i8, i8, i8, i8, i8, i8, i8,
-                 i8, i8, i8, i8, i8, i8, i8, i8);
-                 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool8x16(i8, i8, i8, i8, i8, i8, i8, i8,
-                     i8, i8, i8, i8, i8, i8, i8, i8);
+pub type u16x8 = Simd<u16, 8>;
+pub type i16x8 = Simd<i16, 8>;
+pub type bool16x8 = Mask<i16, 8>;
 
+/// 16x8-bit integer vectors
+pub type u8x16 = Simd<u8, 16>;
+pub type i8x16 = Simd<i8, 16>;
+pub type bool8x16 = Mask<i8, 16>;
 
 /// 2x64-bit vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u64x2(u64, u64);
+pub type u64x2 = Simd<u64, 2>;
+pub type i64x2 = Simd<i64, 2>;
+pub type f64x2 = Simd<f64, 2>;
+pub type bool64x2 = Mask<i64, 2>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i64x2(i64, i64);
+/// 4x64-bit vectors
+pub type u64x4 = Simd<u64, 4>;
+pub type i64x4 = Simd<i64, 4>;
+pub type f64x4 = Simd<f64, 4>;
+pub type bool64x4 = Mask<i64, 4>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct f64x2(pub f64, pub f64);
+/// 8x32-bit vectors
+pub type u32x8 = Simd<u32, 8>;
+pub type i32x8 = Simd<i32, 8>;
+pub type f32x8 = Simd<f32, 8>;
+pub type bool32x8 = Mask<i32, 8>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool64x2(i64, i64);
+/// 16x16-bit integer vectors
+pub type u16x16 = Simd<u16, 16>;
+pub type i16x16 = Simd<i16, 16>;
+pub type bool16x16 = Mask<i16, 16>;
 
+/// 32x8-bit integer vectors
+pub type u8x32 = Simd<u8, 32>;
+pub type i8x32 = Simd<i8, 32>;
+pub type bool8x32 = Mask<i8, 32>;
 
-/// 4x64-bit vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u64x4(u64, u64, u64, u64);
+/// 16x32-bit vectors
+pub type u32x16 = Simd<u32, 16>;
+pub type i32x16 = Simd<i32, 16>;
+pub type f32x16 = Simd<f32, 16>;
+pub type bool32x16 = Mask<i32, 16>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i64x4(i64, i64, i64, i64);
+/// 8x64-bit vectors
+pub type u64x8 = Simd<u64, 8>;
+pub type i64x8 = Simd<i64, 8>;
+pub type f64x8 = Simd<f64, 8>;
+pub type bool64x8 = Mask<i64, 8>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct f64x4(f64, f64, f64, f64);
+/// 32x16-bit integer vectors
+pub type u16x32 = Simd<u16, 32>;
+pub type i16x32 = Simd<i16, 32>;
+pub type bool16x32 = Mask<i16, 32>;
 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool64x4(i64, i64, i64, i64);
+/// 64x8-bit integer vectors
+pub type u8x64 = Simd<u8, 64>;
+pub type i8x64 = Simd<i8, 64>;
+pub type bool8x64 = Mask<i8, 64>;
 
+/// Reduce `lanes` with `f` by repeatedly folding the upper half of the lanes
+/// onto the lower half, e.g. for 4 lanes: `f(f(x0, x2), f(x1, x3))`.
+/// The number of lanes must be a power of two.
+#[inline(always)]
+fn reduce_tree<T: Copy>(lanes: &mut [T], f: fn(T, T) -> T) -> T {
+    let mut n = lanes.len();
+    while n > 1 {
+        n /= 2;
+        for i in 0..n {
+            lanes[i] = f(lanes[i], lanes[i + n]);
+        }
+    }
+    lanes[0]
+}
 
-/// 8x32-bit vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u32x8(u32, u32, u32, u32,
-                 u32, u32, u32, u32);
-                 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i32x8(i32, i32, i32, i32,
-                 i32, i32, i32, i32);
-                 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct f32x8(f32, f32, f32, f32,
-                 f32, f32, f32, f32);
-
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool32x8(i32, i32, i32, i32,
-                     i32, i32, i32, i32);
-                  
-/// 16x16-bit integer vectors
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u16x16(u16, u16, u16, u16, u16, u16, u16, u16,
-                  u16, u16, u16, u16, u16, u16, u16, u16);
-                  
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i16x16(i16, i16, i16, i16, i16, i16, i16, i16,
-                  i16, i16, i16, i16, i16, i16, i16, i16);
-
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool16x16(i16, i16, i16, i16, i16, i16, i16, i16,
-                      i16, i16, i16, i16, i16, i16, i16, i16);
-
-/// 32x8-bit integer vector
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct u8x32(u8, u8, u8, u8, u8, u8, u8, u8,
-                 u8, u8, u8, u8, u8, u8, u8, u8,
-                 u8, u8, u8, u8, u8, u8, u8, u8,
-                 u8, u8, u8, u8, u8, u8, u8, u8);
-                 
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct i8x32(i8, i8, i8, i8, i8, i8, i8, i8,
-                 i8, i8, i8, i8, i8, i8, i8, i8,
-                 i8, i8, i8, i8, i8, i8, i8, i8,
-                 i8, i8, i8, i8, i8, i8, i8, i8);
-
-#[repr(packed)]
-#[derive(Debug, Clone, Copy)]
-pub struct bool8x32(i8, i8, i8, i8, i8, i8, i8, i8,
-                     i8, i8, i8, i8, i8, i8, i8, i8,
-                     i8, i8, i8, i8, i8, i8, i8, i8,
-                     i8, i8, i8, i8, i8, i8, i8, i8);
-
-macro_rules! basic_impls {
-    ($(
-        $name: ident : $elem: ident, 
-        $bool_name: ident : $bool_elem: ident, 
-        $length: expr,
-        $($index:tt : $field:ident),*;
-    )*) => {
-        
-        $(impl $name {
-            /// Create new instance
-            #[inline(always)]
-            pub fn new($($field: $elem),*) -> Self {
-                $name($($field),*)
-            }
-            
-            /// Create new instance with all lanes set to a value
-            #[inline(always)]
-            pub fn splat(x: $elem) -> Self {
-                Self {$($index : x),*}
-            }
-            
-            /// Get the `idx`th lane value
-            #[inline(always)]
-            pub fn extract(self, idx: u32) -> $elem {
-                assert!(idx < $length);
-                unsafe {
-                    let p = (&self) as *const _ as *const $elem;
-                    *(p.offset(idx as isize))
-                }
-            }
-            
-            /// Replace the `idx`th lane with new value
-            #[inline(always)]
-            pub fn replace(self, idx: u32, elem: $elem) -> Self {
-                assert!(idx < $length);
-                unsafe {
-                    let mut ret = self;
-                    let p = (&mut ret) as *mut _ as *mut $elem;
-                    *(p.offset(idx as isize)) = elem;
-                    ret
-                }
-            }
-            
-            /// Load instance from an array
-            #[inline(always)]
-            pub fn load(array: &[$elem], idx: usize) -> Self {
-                $name($(array[idx + $index]),*)
-            }
-            
-            /// Store self to an array
-            #[inline(always)]
-            pub fn store(self, array: &mut [$elem], idx: usize) {
-                $(array[idx + $index] = self.$index);*
-            }
-            
-            /// Compare if equal
-            #[inline(always)]
-            pub fn eq(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index == rhs.$index) as $bool_elem),*)
-            }
-            
-            /// Compare if not equal
-            #[inline(always)]
-            pub fn ne(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index != rhs.$index) as $bool_elem),*)
-            }
-            
-            /// Compare if less than
-            #[inline(always)]
-            pub fn lt(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index < rhs.$index) as $bool_elem),*)
-            }
-            
-            /// Compare if less than or equal
-            #[inline(always)]
-            pub fn le(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index <= rhs.$index) as $bool_elem),*)
+impl<T: SimdElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn from_lanes(lanes: [T; N]) -> Self {
+        Simd(lanes, Default::default())
+    }
+
+    /// Apply `f` to each lane
+    #[inline(always)]
+    fn map<U: SimdElement>(self, f: impl Fn(T) -> U) -> Simd<U, N> {
+        Simd::from_lanes(array::from_fn(|i| f(self.0[i])))
+    }
+
+    /// Apply `f` to each pair of lanes of `self` and `rhs`
+    #[inline(always)]
+    fn zip<U: SimdElement>(self, rhs: Self, f: impl Fn(T, T) -> U) -> Simd<U, N> {
+        Simd::from_lanes(array::from_fn(|i| f(self.0[i], rhs.0[i])))
+    }
+
+    /// Compare each pair of lanes of `self` and `rhs` with `f`
+    #[inline(always)]
+    fn cmp_lanes(self, rhs: Self, f: impl Fn(&T, &T) -> bool) -> Mask<T::Mask, N> {
+        Mask::from_lanes(array::from_fn(|i| T::Mask::from_bool(f(&self.0[i], &rhs.0[i]))))
+    }
+
+    /// Apply `f` to the lanes of `self` and `rhs` where `mask` is true, keep the other lanes of `self`
+    #[inline(always)]
+    fn zip_masked(self, rhs: Self, mask: Mask<T::Mask, N>, f: impl Fn(T, T) -> T) -> Self {
+        Simd::from_lanes(array::from_fn(|i| if mask.0[i].to_bool() { f(self.0[i], rhs.0[i]) } else { self.0[i] }))
+    }
+
+    /// Create new instance with all lanes set to a value
+    #[inline(always)]
+    pub fn splat(x: T) -> Self {
+        Self::from_lanes([x; N])
+    }
+
+    /// Get the `idx`th lane value
+    #[inline(always)]
+    pub fn extract(self, idx: u32) -> T {
+        self.0[idx as usize]
+    }
+
+    /// Replace the `idx`th lane with new value
+    #[inline(always)]
+    pub fn replace(self, idx: u32, elem: T) -> Self {
+        let mut ret = self;
+        ret.0[idx as usize] = elem;
+        ret
+    }
+
+    /// Load instance from an array
+    #[inline(always)]
+    pub fn load(array: &[T], idx: usize) -> Self {
+        let mut lanes = [T::default(); N];
+        lanes.copy_from_slice(&array[idx..idx + N]);
+        Self::from_lanes(lanes)
+    }
+
+    /// Store self to an array
+    #[inline(always)]
+    pub fn store(self, array: &mut [T], idx: usize) {
+        array[idx..idx + N].copy_from_slice(&self.0);
+    }
+
+    /// Get the lanes as an array
+    #[inline(always)]
+    pub fn to_array(self) -> [T; N] {
+        self.0
+    }
+
+    /// Get a reference to the lanes as an array
+    #[inline(always)]
+    pub fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    /// Get a mutable reference to the lanes as an array
+    #[inline(always)]
+    pub fn as_mut_array(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+
+    /// Reinterpret a slice as vectors without copying, like `align_to`.
+    /// Returns the elements before the first aligned vector, the vectors,
+    /// and the elements after them. As with `align_to`, how many elements end
+    /// up in the head and tail is not guaranteed: the head may be the whole
+    /// slice, so the head and tail must be handled with scalar code.
+    #[inline]
+    pub fn slice_from(slice: &[T]) -> (&[T], &[Self], &[T]) {
+        // SAFETY: `Simd<T, N>` is `[T; N]` followed by a zero-sized field,
+        // with no padding, and every bit pattern of `T` is a valid lane.
+        unsafe { slice.align_to() }
+    }
+
+    /// Mutable version of `slice_from`
+    #[inline]
+    pub fn slice_from_mut(slice: &mut [T]) -> (&mut [T], &mut [Self], &mut [T]) {
+        // SAFETY: see `slice_from`
+        unsafe { slice.align_to_mut() }
+    }
+
+    /// Reinterpret a slice of vectors as a slice of its lanes without copying
+    #[inline]
+    pub fn as_flattened(vectors: &[Self]) -> &[T] {
+        // SAFETY: `Simd<T, N>` has the size of `[T; N]`, so the vectors are
+        // `N * len` contiguous lanes.
+        unsafe { slice::from_raw_parts(vectors.as_ptr() as *const T, vectors.len() * N) }
+    }
+
+    /// Mutable version of `as_flattened`
+    #[inline]
+    pub fn as_flattened_mut(vectors: &mut [Self]) -> &mut [T] {
+        // SAFETY: see `as_flattened`
+        unsafe { slice::from_raw_parts_mut(vectors.as_mut_ptr() as *mut T, vectors.len() * N) }
+    }
+
+    /// Load up to `N` lanes from an array, starting at `idx`.
+    /// Lanes past the end of the array are set to `fill`.
+    /// Panics if `idx` is greater than the length of the array.
+    #[inline]
+    pub fn load_partial(array: &[T], idx: usize, fill: T) -> Self {
+        let array = &array[idx..];
+        Self::from_lanes(array::from_fn(|i| if i < array.len() { array[i] } else { fill }))
+    }
+
+    /// Store up to `N` lanes to an array, starting at `idx`.
+    /// Lanes that would go past the end of the array are dropped.
+    /// Panics if `idx` is greater than the length of the array.
+    #[inline]
+    pub fn store_partial(self, array: &mut [T], idx: usize) {
+        let array = &mut array[idx..];
+        let len = array.len().min(N);
+        array[..len].copy_from_slice(&self.0[..len]);
+    }
+
+    /// Load the lanes where `mask` is true from an array, starting at `idx`.
+    /// The other lanes, and the lanes past the end of the array, are taken
+    /// from `or`. The array is never read outside of the lanes that are loaded.
+    /// Panics if `idx` is greater than the length of the array.
+    #[inline]
+    pub fn load_masked(array: &[T], idx: usize, mask: Mask<T::Mask, N>, or: Self) -> Self {
+        let array = &array[idx..];
+        Self::from_lanes(array::from_fn(|i| {
+            match array.get(i) {
+                Some(x) if mask.0[i].to_bool() => *x,
+                _ => or.0[i],
             }
-            
-            /// Compare if greater than
-            #[inline(always)]
-            pub fn gt(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index > rhs.$index) as $bool_elem),*)
+        }))
+    }
+
+    /// Store the lanes where `mask` is true to an array, starting at `idx`.
+    /// Lanes that would go past the end of the array are dropped.
+    /// Panics if `idx` is greater than the length of the array.
+    #[inline]
+    pub fn store_masked(self, array: &mut [T], idx: usize, mask: Mask<T::Mask, N>) {
+        let array = &mut array[idx..];
+        for i in 0..N.min(array.len()) {
+            if mask.0[i].to_bool() {
+                array[i] = self.0[i];
             }
-            
-            /// Compare if greater than or equal
-            #[inline(always)]
-            pub fn ge(self, rhs: Self) -> $bool_name {
-                $bool_name($((self.$index >= rhs.$index) as $bool_elem),*)
+        }
+    }
+
+    /// Load each lane from `base[idx]`, where `idx` is the matching lane of
+    /// the index vector.
+    /// Panics if any index is out of bounds.
+    #[inline]
+    pub fn gather<I: IndexElement>(base: &[T], idx: Simd<I, N>) -> Self {
+        Self::from_lanes(array::from_fn(|i| base[idx.0[i].lane_index()]))
+    }
+
+    /// Load the lanes where `mask` is true from `base[idx]`, take the other
+    /// lanes from `or`. Indices of lanes where `mask` is false are not used.
+    /// Panics if the index of a true lane is out of bounds.
+    #[inline]
+    pub fn gather_masked<I: IndexElement>(base: &[T], idx: Simd<I, N>, mask: Mask<T::Mask, N>,
+                                          or: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| {
+            if mask.0[i].to_bool() { base[idx.0[i].lane_index()] } else { or.0[i] }
+        }))
+    }
+
+    /// Same as `gather`, without bounds checking
+    ///
+    /// # Safety
+    /// Every index must be in bounds of `base`.
+    #[inline]
+    pub unsafe fn gather_unchecked<I: IndexElement>(base: &[T], idx: Simd<I, N>) -> Self {
+        Self::from_lanes(array::from_fn(|i| *base.get_unchecked(idx.0[i].lane_index())))
+    }
+
+    /// Same as `gather_masked`, without bounds checking
+    ///
+    /// # Safety
+    /// The index of every lane where `mask` is true must be in bounds of `base`.
+    #[inline]
+    pub unsafe fn gather_masked_unchecked<I: IndexElement>(base: &[T], idx: Simd<I, N>,
+                                                           mask: Mask<T::Mask, N>, or: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| {
+            if mask.0[i].to_bool() { *base.get_unchecked(idx.0[i].lane_index()) } else { or.0[i] }
+        }))
+    }
+
+    /// Store each lane to `base[idx]`, where `idx` is the matching lane of
+    /// the index vector. Lanes are stored in order, so when two lanes have the
+    /// same index, the last one wins.
+    /// Panics if any index is out of bounds, before anything is stored.
+    #[inline]
+    pub fn scatter<I: IndexElement>(self, base: &mut [T], idx: Simd<I, N>) {
+        self.scatter_masked(base, idx, Mask::splat(true))
+    }
+
+    /// Store the lanes where `mask` is true to `base[idx]`, in order.
+    /// Indices of lanes where `mask` is false are not used.
+    /// Panics if the index of a true lane is out of bounds, before anything is stored.
+    #[inline]
+    pub fn scatter_masked<I: IndexElement>(self, base: &mut [T], idx: Simd<I, N>, mask: Mask<T::Mask, N>) {
+        let len = base.len();
+        for i in 0..N {
+            if mask.0[i].to_bool() {
+                let j = idx.0[i].lane_index();
+                assert!(j < len, "scatter index out of bounds: the len is {} but the index is {}", len, j);
             }
-            
-            /// Get max values by lane
-            #[inline(always)]
-            pub fn max(self, rhs: Self) -> Self {
-                $name($(if self.$index > rhs.$index { self.$index } else {rhs.$index}),*)
+        }
+        // SAFETY: all the indices of true lanes were checked above
+        unsafe { self.scatter_masked_unchecked(base, idx, mask) }
+    }
+
+    /// Same as `scatter`, without bounds checking
+    ///
+    /// # Safety
+    /// Every index must be in bounds of `base`.
+    #[inline]
+    pub unsafe fn scatter_unchecked<I: IndexElement>(self, base: &mut [T], idx: Simd<I, N>) {
+        self.scatter_masked_unchecked(base, idx, Mask::splat(true))
+    }
+
+    /// Same as `scatter_masked`, without bounds checking
+    ///
+    /// # Safety
+    /// The index of every lane where `mask` is true must be in bounds of `base`.
+    #[inline]
+    pub unsafe fn scatter_masked_unchecked<I: IndexElement>(self, base: &mut [T], idx: Simd<I, N>,
+                                                            mask: Mask<T::Mask, N>) {
+        for i in 0..N {
+            if mask.0[i].to_bool() {
+                *base.get_unchecked_mut(idx.0[i].lane_index()) = self.0[i];
             }
-            
-            /// Get min values by lane            
+        }
+    }
+
+    /// Compare if equal
+    #[inline(always)]
+    pub fn eq(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::eq)
+    }
+
+    /// Compare if not equal
+    #[inline(always)]
+    pub fn ne(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::ne)
+    }
+
+    /// Compare if less than
+    #[inline(always)]
+    pub fn lt(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::lt)
+    }
+
+    /// Compare if less than or equal
+    #[inline(always)]
+    pub fn le(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::le)
+    }
+
+    /// Compare if greater than
+    #[inline(always)]
+    pub fn gt(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::gt)
+    }
+
+    /// Compare if greater than or equal
+    #[inline(always)]
+    pub fn ge(self, rhs: Self) -> Mask<T::Mask, N> {
+        self.cmp_lanes(rhs, T::ge)
+    }
+
+    /// Get max values by lane
+    #[inline(always)]
+    pub fn max(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| if a > b { a } else { b })
+    }
+
+    /// Get min values by lane
+    #[inline(always)]
+    pub fn min(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| if a < b { a } else { b })
+    }
+
+    /// Get the sum of all lanes.
+    /// The upper half of the lanes is added to the lower half until one lane is
+    /// left, e.g. for 4 lanes: `(x0 + x2) + (x1 + x3)`.
+    /// Integer sums wrap on overflow, like the `+` operator.
+    #[inline(always)]
+    pub fn sum(self) -> T {
+        reduce_tree(&mut { self.0 }, T::lane_add)
+    }
+
+    /// Get the product of all lanes, in the same order as `sum`
+    #[inline(always)]
+    pub fn product(self) -> T {
+        reduce_tree(&mut { self.0 }, T::lane_mul)
+    }
+
+    /// Get the smallest lane value
+    #[inline(always)]
+    pub fn min_element(self) -> T {
+        reduce_tree(&mut { self.0 }, |a, b| if a < b { a } else { b })
+    }
+
+    /// Get the largest lane value
+    #[inline(always)]
+    pub fn max_element(self) -> T {
+        reduce_tree(&mut { self.0 }, |a, b| if a > b { a } else { b })
+    }
+
+    /// Add `rhs` to the lanes where `mask` is true, keep the other lanes of `self`
+    #[inline(always)]
+    pub fn add_masked(self, rhs: Self, mask: Mask<T::Mask, N>) -> Self {
+        self.zip_masked(rhs, mask, T::lane_add)
+    }
+
+    /// Subtract `rhs` from the lanes where `mask` is true, keep the other lanes of `self`
+    #[inline(always)]
+    pub fn sub_masked(self, rhs: Self, mask: Mask<T::Mask, N>) -> Self {
+        self.zip_masked(rhs, mask, T::lane_sub)
+    }
+
+    /// Multiply by `rhs` the lanes where `mask` is true, keep the other lanes of `self`
+    #[inline(always)]
+    pub fn mul_masked(self, rhs: Self, mask: Mask<T::Mask, N>) -> Self {
+        self.zip_masked(rhs, mask, T::lane_mul)
+    }
+
+    /// Divide by `rhs` the lanes where `mask` is true, keep the other lanes of `self`.
+    /// Lanes where `mask` is false are never divided, so they may hold a zero divisor.
+    #[inline(always)]
+    pub fn div_masked(self, rhs: Self, mask: Mask<T::Mask, N>) -> Self {
+        self.zip_masked(rhs, mask, T::div)
+    }
+}
+
+impl<T: SimdElement, const N: usize> From<[T; N]> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn from(array: [T; N]) -> Self {
+        Simd::from_lanes(array)
+    }
+}
+
+impl<T: SimdElement, const N: usize> From<Simd<T, N>> for [T; N] where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn from(v: Simd<T, N>) -> Self {
+        v.0
+    }
+}
+
+/// Index trait (v[i]): get the `i`th lane, like `extract`
+impl<T: SimdElement, const N: usize> Index<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = T;
+    #[inline(always)]
+    fn index(&self, idx: usize) -> &T {
+        &self.0[idx]
+    }
+}
+
+/// IndexMut trait (v[i] = x): set the `i`th lane in place
+impl<T: SimdElement, const N: usize> IndexMut<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        &mut self.0[idx]
+    }
+}
+
+/// Add trait (+).
+/// Integer lanes wrap on overflow in both debug and release builds,
+/// like `wrapping_add`.
+impl<T: SimdElement, const N: usize> Add for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_add)
+    }
+}
+
+/// Sub trait (-).
+/// Integer lanes wrap on overflow in both debug and release builds,
+/// like `wrapping_sub`.
+impl<T: SimdElement, const N: usize> Sub for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_sub)
+    }
+}
+
+/// Mul trait (*).
+/// Integer lanes keep the low half of the product in both debug and release
+/// builds, like `wrapping_mul`.
+impl<T: SimdElement, const N: usize> Mul for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_mul)
+    }
+}
+
+/// Div trait (/).
+/// Integer lanes panic on division by zero and on `MIN / -1`, as scalar division does.
+impl<T: SimdElement, const N: usize> Div for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn div(self, rhs: Self) -> Self {
+        self.zip(rhs, T::div)
+    }
+}
+
+// Compound assignment and scalar operators, in terms of the vector operator:
+// `v + x` and `x + v` are exactly `v + Simd::splat(x)` and `Simd::splat(x) + v`
+macro_rules! assign_ops_impls {
+    ($bound: ident, $($op: ident $op_fn: ident $assign: ident $assign_fn: ident),*) => {
+        $(impl<T: $bound, const N: usize> $assign for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
             #[inline(always)]
-            pub fn min(self, rhs: Self) -> Self {
-                $name($(if self.$index < rhs.$index { self.$index } else {rhs.$index}),*)
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$op_fn(*self, rhs);
             }
-        })*
-        
-        /// Add trait (+)
-        $(impl Add for $name {            
+        }
+
+        impl<T: $bound, const N: usize> $op<T> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
             type Output = Self;
             #[inline(always)]
-            fn add(self, rhs: Self) -> Self {
-                $name($(self.$index + rhs.$index),*)
+            fn $op_fn(self, rhs: T) -> Self {
+                $op::$op_fn(self, Simd::splat(rhs))
             }
-        })*
-        
-        /// Sub trait (-)
-        $(impl Sub for $name {            
-            type Output = Self;
+        }
+
+        impl<T: $bound, const N: usize> $assign<T> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
             #[inline(always)]
-            fn sub(self, rhs: Self) -> Self {
-                $name($(self.$index - rhs.$index),*)
+            fn $assign_fn(&mut self, rhs: T) {
+                *self = $op::$op_fn(*self, Simd::splat(rhs));
             }
         })*
-                
-        /// Mul trait (*)
-        $(impl Mul for $name {            
-            type Output = Self;
+    }
+}
+
+macro_rules! scalar_lhs_ops_impls {
+    ($($elem: ident),*; $ops: tt) => {
+        $(scalar_lhs_ops_impls! { @elem $elem; $ops })*
+    };
+    (@elem $elem: ident; [$($op: ident $op_fn: ident),*]) => {
+        $(impl<const N: usize> $op<Simd<$elem, N>> for $elem where LaneCount<N>: SupportedLaneCount {
+            type Output = Simd<$elem, N>;
             #[inline(always)]
-            fn mul(self, rhs: Self) -> Self {
-                $name($(self.$index * rhs.$index),*)
+            fn $op_fn(self, rhs: Simd<$elem, N>) -> Simd<$elem, N> {
+                $op::$op_fn(Simd::splat(self), rhs)
             }
         })*
-        
-        /// Div trait (/)
-        $(impl Div for $name {            
+    }
+}
+
+assign_ops_impls! { SimdElement, Add add AddAssign add_assign, Sub sub SubAssign sub_assign,
+                                 Mul mul MulAssign mul_assign, Div div DivAssign div_assign }
+
+scalar_lhs_ops_impls! { u8, i8, u16, i16, u32, i32, u64, i64, f32, f64; [Add add, Sub sub, Mul mul, Div div] }
+
+impl<T: IntElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Select between elements of `then` and `else_`, based on
+    /// the corresponding element of `self`.
+    /// Equivalent to:
+    /// T::new(if self.0 { then.0 } else { else_.0 },
+    ///        if self.1 { then.1 } else { else_.1 },
+    ///        ...)
+    /// Each lane of `self` must be all ones (true) or all zeros (false),
+    /// which is what `to_i`/`to_u` produce from a boolean vector.
+    /// To select with a boolean vector directly, use `Mask::select`.
+    #[inline(always)]
+    pub fn select(&self, then: Self, else_ : Self) -> Self {
+        (*self & then) | (!*self & else_)
+    }
+
+    /// Add lanes, wrapping around at the bounds of the element type
+    #[inline(always)]
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_add)
+    }
+
+    /// Subtract lanes, wrapping around at the bounds of the element type
+    #[inline(always)]
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_sub)
+    }
+
+    /// Multiply lanes, keeping the low half of each product
+    #[inline(always)]
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_mul)
+    }
+
+    /// Add lanes, clamping the result to the bounds of the element type.
+    /// For 8-bit and 16-bit lanes this compiles to `paddus`/`padds`
+    /// (`vpaddus`/`vpadds` with AVX2).
+    #[inline(always)]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_saturating_add)
+    }
+
+    /// Subtract lanes, clamping the result to the bounds of the element type.
+    /// For 8-bit and 16-bit lanes this compiles to `psubus`/`psubs`
+    /// (`vpsubus`/`vpsubs` with AVX2).
+    #[inline(always)]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_saturating_sub)
+    }
+
+    /// Shift each lane left by `n` modulo the lane width
+    #[inline(always)]
+    pub fn wrapping_shl(self, n: u32) -> Self {
+        self.map(|x| x.lane_wrapping_shl(n))
+    }
+
+    /// Shift each lane right by `n` modulo the lane width
+    #[inline(always)]
+    pub fn wrapping_shr(self, n: u32) -> Self {
+        self.map(|x| x.lane_wrapping_shr(n))
+    }
+
+    /// Rotate the bits of each lane left by `n`, modulo the lane width
+    #[inline(always)]
+    pub fn rotate_left(self, n: u32) -> Self {
+        self.map(|x| x.lane_rotate_left(n))
+    }
+
+    /// Rotate the bits of each lane right by `n`, modulo the lane width
+    #[inline(always)]
+    pub fn rotate_right(self, n: u32) -> Self {
+        self.map(|x| x.lane_rotate_right(n))
+    }
+
+    /// Rotate the bits of each lane left by the corresponding lane of `n`,
+    /// modulo the lane width
+    #[inline(always)]
+    pub fn rotate_left_by(self, n: Self) -> Self {
+        self.zip(n, |x, n| x.lane_rotate_left(n.lane_rotate_count()))
+    }
+
+    /// Rotate the bits of each lane right by the corresponding lane of `n`,
+    /// modulo the lane width
+    #[inline(always)]
+    pub fn rotate_right_by(self, n: Self) -> Self {
+        self.zip(n, |x, n| x.lane_rotate_right(n.lane_rotate_count()))
+    }
+
+    /// Get the bitwise and of all lanes
+    #[inline(always)]
+    pub fn and(self) -> T {
+        reduce_tree(&mut { self.0 }, |a, b| a & b)
+    }
+
+    /// Get the bitwise or of all lanes
+    #[inline(always)]
+    pub fn or(self) -> T {
+        reduce_tree(&mut { self.0 }, |a, b| a | b)
+    }
+
+    /// Get the bitwise xor of all lanes
+    #[inline(always)]
+    pub fn xor(self) -> T {
+        reduce_tree(&mut { self.0 }, |a, b| a ^ b)
+    }
+}
+
+/// BitAnd trait (&)
+impl<T: IntElement, const N: usize> BitAnd for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitand(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitand)
+    }
+}
+
+/// BitOr trait (|)
+impl<T: IntElement, const N: usize> BitOr for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitor)
+    }
+}
+
+/// BitXor trait (^)
+impl<T: IntElement, const N: usize> BitXor for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitxor)
+    }
+}
+
+/// Not trait (!)
+impl<T: IntElement, const N: usize> Not for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn not(self) -> Self {
+        self.map(T::not)
+    }
+}
+
+/// Rem trait (%).
+/// Lanes panic on a zero divisor and on `MIN % -1`, as scalar remainder does.
+impl<T: IntElement, const N: usize> Rem for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn rem(self, rhs: Self) -> Self {
+        self.zip(rhs, T::rem)
+    }
+}
+
+/// Shift left trait (<<).
+/// Shifting by the lane width or more gives 0.
+impl<T: IntElement, const N: usize> Shl<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn shl(self, sz: usize) -> Self {
+        let sz = sz.min(u32::MAX as usize) as u32;
+        self.map(|x| x.lane_shl(sz))
+    }
+}
+
+/// Shift right trait (>>), arithmetic for signed lanes and logical for unsigned lanes.
+/// Shifting by the lane width or more gives 0, or -1 for negative signed lanes.
+impl<T: IntElement, const N: usize> Shr<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn shr(self, sz: usize) -> Self {
+        let sz = sz.min(u32::MAX as usize) as u32;
+        self.map(|x| x.lane_shr(sz))
+    }
+}
+
+/// Shift left trait (<<), shifting each lane by the corresponding lane of `rhs`
+/// like `vpsllv`. Counts are read as unsigned, and counts of the lane width or
+/// more give 0.
+impl<T: IntElement, const N: usize> Shl for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn shl(self, rhs: Self) -> Self {
+        self.zip(rhs, |x, n| x.lane_shl(n.lane_shift_count()))
+    }
+}
+
+/// Shift right trait (>>), shifting each lane by the corresponding lane of `rhs`
+/// like `vpsrlv`/`vpsrav`. Counts are read as unsigned, and counts of the lane
+/// width or more give 0, or -1 for negative signed lanes.
+impl<T: IntElement, const N: usize> Shr for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn shr(self, rhs: Self) -> Self {
+        self.zip(rhs, |x, n| x.lane_shr(n.lane_shift_count()))
+    }
+}
+
+/// ShlAssign trait (<<=)
+impl<T: IntElement, const N: usize> ShlAssign<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn shl_assign(&mut self, sz: usize) {
+        *self = *self << sz;
+    }
+}
+
+/// ShrAssign trait (>>=)
+impl<T: IntElement, const N: usize> ShrAssign<usize> for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn shr_assign(&mut self, sz: usize) {
+        *self = *self >> sz;
+    }
+}
+
+/// ShlAssign trait (<<=) by a vector of counts
+impl<T: IntElement, const N: usize> ShlAssign for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn shl_assign(&mut self, rhs: Self) {
+        *self = *self << rhs;
+    }
+}
+
+/// ShrAssign trait (>>=) by a vector of counts
+impl<T: IntElement, const N: usize> ShrAssign for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn shr_assign(&mut self, rhs: Self) {
+        *self = *self >> rhs;
+    }
+}
+
+assign_ops_impls! { IntElement, Rem rem RemAssign rem_assign, BitAnd bitand BitAndAssign bitand_assign,
+                                BitOr bitor BitOrAssign bitor_assign, BitXor bitxor BitXorAssign bitxor_assign }
+
+scalar_lhs_ops_impls! { u8, i8, u16, i16, u32, i32, u64, i64; [Rem rem, BitAnd bitand, BitOr bitor, BitXor bitxor] }
+
+impl<T: MaskElement, const N: usize> Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn from_lanes(lanes: [T; N]) -> Self {
+        Mask(lanes, Default::default())
+    }
+
+    /// Apply `f` to each pair of lanes of `self` and `rhs`
+    #[inline(always)]
+    fn zip(self, rhs: Self, f: impl Fn(T, T) -> T) -> Self {
+        Mask::from_lanes(array::from_fn(|i| f(self.0[i], rhs.0[i])))
+    }
+
+    /// Create new instance with all lanes set to a value
+    #[inline(always)]
+    pub fn splat(x: bool) -> Self {
+        Self::from_lanes([T::from_bool(x); N])
+    }
+
+    /// Get the `idx`th lane value
+    #[inline(always)]
+    pub fn extract(self, idx: u32) -> bool {
+        self.0[idx as usize].to_bool()
+    }
+
+    /// Replace the `idx`th lane with new value
+    #[inline(always)]
+    pub fn replace(self, idx: u32, elem: bool) -> Self {
+        let mut ret = self;
+        ret.0[idx as usize] = T::from_bool(elem);
+        ret
+    }
+
+    /// Load instance from an array
+    #[inline(always)]
+    pub fn load(array: &[bool], idx: usize) -> Self {
+        let array = &array[idx..idx + N];
+        Self::from_lanes(array::from_fn(|i| T::from_bool(array[i])))
+    }
+
+    /// Store self to an array
+    #[inline(always)]
+    pub fn store(self, array: &mut [bool], idx: usize) {
+        for (x, lane) in array[idx..idx + N].iter_mut().zip(self.0.iter()) {
+            *x = lane.to_bool();
+        }
+    }
+
+    /// Check if all lanes are true
+    #[inline(always)]
+    pub fn all(self) -> bool {
+        self.0.iter().all(|x| x.to_bool())
+    }
+
+    /// Check if any lane is true
+    #[inline(always)]
+    pub fn any(self) -> bool {
+        self.0.iter().any(|x| x.to_bool())
+    }
+
+    /// Get the lanes as signed integers: -1 for true and 0 for false
+    #[inline(always)]
+    pub fn to_i(self) -> Simd<T, N> {
+        Simd::from_lanes(self.0)
+    }
+
+    /// Get the lanes as unsigned integers: all ones for true and 0 for false
+    #[inline(always)]
+    pub fn to_u(self) -> Simd<T::Unsigned, N> {
+        Simd::from_lanes(array::from_fn(|i| self.0[i].to_unsigned()))
+    }
+
+    /// Select between elements of `then` and `else_`, based on
+    /// the corresponding element of `self`.
+    /// Equivalent to:
+    /// T::new(if self.0 { then.0 } else { else_.0 },
+    ///        if self.1 { then.1 } else { else_.1 },
+    ///        ...)
+    /// Works for every vector type whose comparisons return this boolean
+    /// vector, e.g. `bool32x4` selects `f32x4`, `i32x4` and `u32x4` lanes.
+    #[inline(always)]
+    pub fn select<E: SimdElement<Mask = T>>(self, then: Simd<E, N>, else_: Simd<E, N>) -> Simd<E, N> {
+        Simd::from_lanes(array::from_fn(|i| if self.0[i].to_bool() { then.0[i] } else { else_.0[i] }))
+    }
+}
+
+/// BitAnd trait (&)
+impl<T: MaskElement, const N: usize> BitAnd for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitand(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitand)
+    }
+}
+
+/// BitOr trait (|)
+impl<T: MaskElement, const N: usize> BitOr for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitor(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitor)
+    }
+}
+
+/// BitXor trait (^)
+impl<T: MaskElement, const N: usize> BitXor for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self {
+        self.zip(rhs, T::bitxor)
+    }
+}
+
+/// Not trait (!)
+impl<T: MaskElement, const N: usize> Not for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Output = Self;
+    #[inline(always)]
+    fn not(self) -> Self {
+        Mask::from_lanes(array::from_fn(|i| !self.0[i]))
+    }
+}
+
+/// BitAndAssign trait (&=)
+impl<T: MaskElement, const N: usize> BitAndAssign for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+/// BitOrAssign trait (|=)
+impl<T: MaskElement, const N: usize> BitOrAssign for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+/// BitXorAssign trait (^=)
+impl<T: MaskElement, const N: usize> BitXorAssign for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+/// Lane selection driven by a boolean vector, for generic code such as
+/// `V::Mask: Select<V>`. It forwards to `Mask::select`, which needs no import.
+/// This trait is sealed: it is only implemented by `Mask`.
+pub trait Select<T>: Sealed {
+    /// Select between elements of `then` and `else_`, based on
+    /// the corresponding element of `self`, like `Mask::select`
+    fn select(self, then: T, else_: T) -> T;
+}
+
+impl<T: SimdElement, const N: usize> Select<Simd<T, N>> for Mask<T::Mask, N> where LaneCount<N>: SupportedLaneCount {
+    #[inline(always)]
+    fn select(self, then: Simd<T, N>, else_: Simd<T, N>) -> Simd<T, N> {
+        Mask::select(self, then, else_)
+    }
+}
+
+impl<T: FloatElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Get square root
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        self.map(T::lane_sqrt)
+    }
+
+    /// Get reciprocal of square root
+    #[inline]
+    pub fn approx_rsqrt(self) -> Self {
+        self.map(|x| x.lane_sqrt().lane_recip())
+    }
+
+    /// Get reciprocal
+    #[inline]
+    pub fn approx_reciprocal(self) -> Self {
+        self.map(T::lane_recip)
+    }
+
+    /// Fused multiply-add: `self * a + b` with a single rounding.
+    /// Lowered to `vfmadd` when the `fma` target feature is enabled,
+    /// otherwise each lane calls the scalar `mul_add` from the standard library.
+    #[inline]
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| self.0[i].lane_mul_add(a.0[i], b.0[i])))
+    }
+
+    /// Fused multiply-subtract: `self * a - b` with a single rounding
+    #[inline]
+    pub fn mul_sub(self, a: Self, b: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| self.0[i].lane_mul_add(a.0[i], -b.0[i])))
+    }
+
+    /// Fused negated multiply-add: `-(self * a) + b` with a single rounding
+    #[inline]
+    pub fn neg_mul_add(self, a: Self, b: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| (-self.0[i]).lane_mul_add(a.0[i], b.0[i])))
+    }
+
+    /// Fused negated multiply-subtract: `-(self * a) - b` with a single rounding
+    #[inline]
+    pub fn neg_mul_sub(self, a: Self, b: Self) -> Self {
+        Self::from_lanes(array::from_fn(|i| (-self.0[i]).lane_mul_add(a.0[i], -b.0[i])))
+    }
+
+    /// Round each lane toward negative infinity
+    #[inline]
+    pub fn floor(self) -> Self {
+        self.map(T::lane_floor)
+    }
+
+    /// Round each lane toward positive infinity
+    #[inline]
+    pub fn ceil(self) -> Self {
+        self.map(T::lane_ceil)
+    }
+
+    /// Round each lane to the nearest integer, with halfway cases away from zero
+    #[inline]
+    pub fn round(self) -> Self {
+        self.map(T::lane_round)
+    }
+
+    /// Round each lane to the nearest integer, with halfway cases to the even integer.
+    /// This is the default rounding mode of `roundps`/`cvtps2dq`.
+    #[inline]
+    pub fn round_ties_even(self) -> Self {
+        self.map(T::lane_round_ties_even)
+    }
+
+    /// Round each lane toward zero
+    #[inline]
+    pub fn trunc(self) -> Self {
+        self.map(T::lane_trunc)
+    }
+
+    /// Get the fractional part of each lane, `self - self.trunc()`.
+    /// The result has the sign of `self`.
+    #[inline]
+    pub fn fract(self) -> Self {
+        self.map(T::lane_fract)
+    }
+
+    /// Get the sum of all lanes, added strictly from left to right:
+    /// `((x0 + x1) + x2) + ...`.
+    /// Unlike `sum`, the result matches a sequential scalar loop exactly.
+    #[inline]
+    pub fn ordered_sum(self) -> T {
+        let mut acc = self.0[0];
+        for x in &self.0[1..] {
+            acc = acc + *x;
+        }
+        acc
+    }
+}
+
+macro_rules! neg_impls {
+    ($($elem: ident),*) => {
+        /// Neg trait (-).
+        /// Signed integer lanes wrap, so `-MIN` is `MIN` in every build profile.
+        $(impl<const N: usize> Neg for Simd<$elem, N> where LaneCount<N>: SupportedLaneCount {
             type Output = Self;
             #[inline(always)]
-            fn div(self, rhs: Self) -> Self {
-                $name($(self.$index / rhs.$index),*)
+            fn neg(self) -> Self {
+                self.map(SimdElement::lane_neg)
             }
         })*
     }
 }
 
-basic_impls! {
-    u32x2:u32, bool32x2:i32, 2, 0:x0, 1:x1;
-    i32x2:i32, bool32x2:i32, 2, 0:x0, 1:x1;
-    f32x2:f32, bool32x2:i32, 2, 0:x0, 1:x1;
-    
-    u32x4:u32, bool32x4:i32, 4, 0:x0, 1:x1, 2:x2, 3:x3;
-    i32x4:i32, bool32x4:i32, 4, 0:x0, 1:x1, 2:x2, 3:x3;
-    f32x4:f32, bool32x4:i32, 4, 0:x0, 1:x1, 2:x2, 3:x3;
-    
-    u16x8:u16, bool16x8:i16, 8, 0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    i16x8:i16, bool16x8:i16, 8, 0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-   
-    u64x2:u64, bool64x2:i64, 2, 0:x0, 1:x1;
-    i64x2:i64, bool64x2:i64, 2, 0:x0, 1:x1;
-    f64x2:f64, bool64x2:i64, 2, 0:x0, 1:x1;
-    
-    u64x4:u64, bool64x4:i64, 4, 0:x0, 1:x1 ,2:x2, 3:x3;
-    i64x4:i64, bool64x4:i64, 4, 0:x0, 1:x1 ,2:x2, 3:x3;
-    f64x4:f64, bool64x4:i64, 4, 0:x0, 1:x1 ,2:x2, 3:x3;
-    
-    u32x8:u32, bool32x8:i32, 8, 0:x0, 1:x1 ,2:x2, 3:x3, 4:x4, 5:x5 ,6:x6, 7:x7;
-    i32x8:i32, bool32x8:i32, 8, 0:x0, 1:x1 ,2:x2, 3:x3, 4:x4, 5:x5 ,6:x6, 7:x7;
-    f32x8:f32, bool32x8:i32, 8, 0:x0, 1:x1 ,2:x2, 3:x3, 4:x4, 5:x5 ,6:x6, 7:x7;
-    
-    u16x16:u16, bool16x16:i16, 16,  0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                                    8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                               
-    i16x16:i16, bool16x16:i16, 16,  0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                                    8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                               
-    u8x32:u8, bool8x32:i8, 32,      0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                                    8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15,
-                                    16:x16, 17:x17 , 18:x18, 19:x19, 20:x20, 21:x21 ,22:x22, 23:x23,
-                                    24:x24, 25:x25 , 26:x26, 27:x27, 28:x28, 29:x29 ,30:x30, 31:x31;
-                            
-    i8x32:i8, bool8x32:i8, 32,      0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                                    8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15,
-                                    16:x16, 17:x17 , 18:x18, 19:x19, 20:x20, 21:x21 ,22:x22, 23:x23,
-                                    24:x24, 25:x25 , 26:x26, 27:x27, 28:x28, 29:x29 ,30:x30, 31:x31;
-}
-
-macro_rules! int_impls {
+neg_impls! { i8, i16, i32, i64, f32, f64 }
+
+macro_rules! new_impls {
     ($(
-        $name: ident : $elem: ident, 
-        $($index:tt : $field:ident),*;
+        $name: ident : $elem: ident,
+        $($field: ident),*;
         )*) => {
-        
+
         $(impl $name {
-            /// Select between elements of `then` and `else_`, based on
-            /// the corresponding element of `self`.
-            /// Equivalent to:
-            /// T::new(if self.0 { then.0 } else { else_.0 },
-            ///        if self.1 { then.1 } else { else_.1 },
-            ///        ...)
-            #[inline(always)]
-            pub fn select(&self, then: Self, else_ : Self) -> Self {
-                $name($((self.$index & then.$index) | (!self.$index & else_.$index)),*)
-            }
-        })*
-        
-        /// BitAnd trait (&)
-        $(impl BitAnd for $name {
-            type Output = Self;
-            #[inline(always)]
-            fn bitand(self, rhs: Self) -> Self {
-                $name($(self.$index & rhs.$index),*)
-            }
-        })*
-            
-        /// BitOr trait (|)
-        $(impl BitOr for $name {            
-            type Output = Self;
-            #[inline(always)]
-            fn bitor(self, rhs: Self) -> Self {
-                $name($(self.$index | rhs.$index),*)
-            }
-        })*
-        
-        /// BitXor trait (^)        
-        $(impl BitXor for $name {            
-            type Output = Self;
+            /// Create new instance
             #[inline(always)]
-            fn bitxor(self, rhs: Self) -> Self {
-                $name($(self.$index ^ rhs.$index),*)
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($field: $elem),*) -> Self {
+                Self::from_lanes([$($field),*])
             }
         })*
-        
-        /// Not trait (!)
-        $(impl Not for $name {
-            type Output = Self;
+    }
+}
 
+macro_rules! mask_new_impls {
+    ($(
+        $name: ident : $elem: ident,
+        $($field: ident),*;
+        )*) => {
+
+        $(impl $name {
+            /// Create new instance
             #[inline(always)]
-            fn not(self) -> Self {
-              $name($(!self.$index),*)
-            }
-        })*
-        
-        /// Shift left trait (<<)
-        $(impl Shl<usize> for $name {
-            type Output = Self;
-            #[inline(always)]
-            fn shl(self, sz: usize) -> Self {
-                $name($(self.$index << (sz as $elem)),*)
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($field: bool),*) -> Self {
+                Self::from_lanes([$(<$elem as MaskElement>::from_bool($field)),*])
             }
         })*
-        
-        /// Shift right trait (>>)
-        $(impl Shr<usize> for $name {
-            type Output = Self;
+    }
+}
+
+new_impls! {
+    u32x2:u32,      x0, x1;
+    i32x2:i32,      x0, x1;
+    f32x2:f32,      x0, x1;
+    u32x4:u32,      x0, x1, x2, x3;
+    i32x4:i32,      x0, x1, x2, x3;
+    f32x4:f32,      x0, x1, x2, x3;
+    u16x8:u16,      x0, x1, x2, x3, x4, x5, x6, x7;
+    i16x8:i16,      x0, x1, x2, x3, x4, x5, x6, x7;
+    u8x16:u8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    i8x16:i8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    u64x2:u64,      x0, x1;
+    i64x2:i64,      x0, x1;
+    f64x2:f64,      x0, x1;
+    u64x4:u64,      x0, x1, x2, x3;
+    i64x4:i64,      x0, x1, x2, x3;
+    f64x4:f64,      x0, x1, x2, x3;
+    u32x8:u32,      x0, x1, x2, x3, x4, x5, x6, x7;
+    i32x8:i32,      x0, x1, x2, x3, x4, x5, x6, x7;
+    f32x8:f32,      x0, x1, x2, x3, x4, x5, x6, x7;
+    u16x16:u16,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    i16x16:i16,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    u8x32:u8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    i8x32:i8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    u32x16:u32,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    i32x16:i32,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    f32x16:f32,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    u64x8:u64,      x0, x1, x2, x3, x4, x5, x6, x7;
+    i64x8:i64,      x0, x1, x2, x3, x4, x5, x6, x7;
+    f64x8:f64,      x0, x1, x2, x3, x4, x5, x6, x7;
+    u16x32:u16,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    i16x32:i16,     x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    u8x64:u8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31,
+                    x32, x33, x34, x35, x36, x37, x38, x39, x40, x41, x42, x43, x44, x45, x46, x47,
+                    x48, x49, x50, x51, x52, x53, x54, x55, x56, x57, x58, x59, x60, x61, x62, x63;
+    i8x64:i8,       x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31,
+                    x32, x33, x34, x35, x36, x37, x38, x39, x40, x41, x42, x43, x44, x45, x46, x47,
+                    x48, x49, x50, x51, x52, x53, x54, x55, x56, x57, x58, x59, x60, x61, x62, x63;
+}
+
+mask_new_impls! {
+    bool32x2:i32,   x0, x1;
+    bool32x4:i32,   x0, x1, x2, x3;
+    bool16x8:i16,   x0, x1, x2, x3, x4, x5, x6, x7;
+    bool8x16:i8,    x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    bool64x2:i64,   x0, x1;
+    bool64x4:i64,   x0, x1, x2, x3;
+    bool32x8:i32,   x0, x1, x2, x3, x4, x5, x6, x7;
+    bool16x16:i16,  x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    bool8x32:i8,    x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    bool32x16:i32,  x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15;
+    bool64x8:i64,   x0, x1, x2, x3, x4, x5, x6, x7;
+    bool16x32:i16,  x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31;
+    bool8x64:i8,    x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15,
+                    x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31,
+                    x32, x33, x34, x35, x36, x37, x38, x39, x40, x41, x42, x43, x44, x45, x46, x47,
+                    x48, x49, x50, x51, x52, x53, x54, x55, x56, x57, x58, x59, x60, x61, x62, x63;
+}
+
+macro_rules! conv_impls {
+    ($(
+        $cvt: ident, $from_elem: ident -> $to_elem: ident;
+        )*) => {
+
+        $(impl<const N: usize> Simd<$from_elem, N> where LaneCount<N>: SupportedLaneCount {
             #[inline(always)]
-            fn shr(self, sz: usize) -> Self {
-                $name($(self.$index >> (sz as $elem)),*)
+            pub fn $cvt(self) -> Simd<$to_elem, N> {
+                self.map(|x| x as $to_elem)
             }
         })*
     }
 }
 
-int_impls! {
-    u32x2:u32,  0:x0, 1:x1;
-    i32x2:i32,  0:x0, 1:x1;
-    
-    u32x4:u32,  0:x0, 1:x1, 2:x2, 3:x3;
-    i32x4:i32,  0:x0, 1:x1, 2:x2, 3:x3;
-    
-    u16x8:u16,  0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    i16x8:i16,  0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-   
-    u64x2:u64,  0:x0, 1:x1;
-    i64x2:i64,  0:x0, 1:x1;
-    
-    u64x4:u64,  0:x0, 1:x1 ,2:x2, 3:x3;
-    i64x4:i64,  0:x0, 1:x1 ,2:x2, 3:x3;
-    
-    u32x8:u32,  0:x0, 1:x1 ,2:x2, 3:x3, 4:x4, 5:x5 ,6:x6, 7:x7;
-    i32x8:i32,  0:x0, 1:x1 ,2:x2, 3:x3, 4:x4, 5:x5 ,6:x6, 7:x7;
-    
-    u16x16:u16, 0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                
-    i16x16:i16, 0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                               
-    u8x32:u8,   0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15,
-                16:x16, 17:x17 , 18:x18, 19:x19, 20:x20, 21:x21 ,22:x22, 23:x23,
-                24:x24, 25:x25 , 26:x26, 27:x27, 28:x28, 29:x29 ,30:x30, 31:x31;
-                
-    i8x32:i8,   0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15,
-                16:x16, 17:x17 , 18:x18, 19:x19, 20:x20, 21:x21 ,22:x22, 23:x23,
-                24:x24, 25:x25 , 26:x26, 27:x27, 28:x28, 29:x29 ,30:x30, 31:x31;
-}
-
-macro_rules! bool_impls {
+/// Conversion among types
+conv_impls! {
+    to_i, u32 -> i32;
+    to_i, f32 -> i32;
+    to_u, i32 -> u32;
+    to_u, f32 -> u32;
+    to_f, u32 -> f32;
+    to_f, i32 -> f32;
+
+    to_i, u64 -> i64;
+    to_i, f64 -> i64;
+    to_u, i64 -> u64;
+    to_u, f64 -> u64;
+    to_f, u64 -> f64;
+    to_f, i64 -> f64;
+
+    to_i, u8 -> i8;
+    to_u, i8 -> u8;
+
+    to_i32, i64 -> i32;
+    to_i64, i32 -> i64;
+    to_u32, u64 -> u32;
+    to_u64, u32 -> u64;
+    to_f32, f64 -> f32;
+    to_f64, f32 -> f64;
+}
+
+macro_rules! round_conv_impls {
     ($(
-        $name: ident : $elem: ident, 
-        $length: expr,
-        $($index:tt : $field:ident),*;
+        $cvt: ident, $round: ident, $from_elem: ident -> $to_elem: ident;
         )*) => {
-        
-        $(impl $name {
-            /// Create new instance
-            #[inline(always)]
-            pub fn new($($field: $elem),*) -> Self {
-                $name($($field),*)
-            }
 
-            /// Create new instance with all lanes set to a value
-            #[inline(always)]
-            pub fn splat(x: $elem) -> Self {
-                Self {$($index : x),*}
-            }
-            
-            /// Get the `idx`th lane value
-            #[inline(always)]
-            pub fn extract(self, idx: u32) -> $elem {
-                assert!(idx < $length);
-                unsafe {
-                    let p = (&self) as *const _ as *const $elem;
-                    *(p.offset(idx as isize))
-                }
-            }
-            
-            /// Replace the `idx`th lane with new value
-            #[inline(always)]
-            pub fn replace(self, idx: u32, elem: $elem) -> Self {
-                assert!(idx < $length);
-                unsafe {
-                    let mut ret = self;
-                    let p = (&mut ret) as *mut _ as *mut $elem;
-                    *(p.offset(idx as isize)) = elem;
-                    ret
-                }
-            }
-            
-            /// Load instance from an array
-            #[inline(always)]
-            pub fn load(array: &[$elem], idx: usize) -> Self {
-                $name($(array[idx + $index]),*)
-            }
-            
-            /// Store self to an array
+        $(impl<const N: usize> Simd<$from_elem, N> where LaneCount<N>: SupportedLaneCount {
+            /// Round each lane with the float method of the same suffix, then convert.
+            /// Out-of-range lanes saturate to the integer limits and NaN becomes 0.
             #[inline(always)]
-            pub fn store(self, array: &mut [$elem], idx: usize) {
-                $(array[idx + $index] = self.$index);*
+            pub fn $cvt(self) -> Simd<$to_elem, N> {
+                self.map(|x| x.$round() as $to_elem)
             }
-            
-            /// Check if all lanes are true
-            #[inline(always)]
-            pub fn all(self) -> bool {
-                $((self.$index != 0)) && *
-            }
-            
-            /// Check if at least one lane is true
-            #[inline(always)]
-            pub fn any(self) -> bool {
-                $((self.$index != 0)) || *
-            }            
-       })*
-       
-       /// Not trait (!)
-       $(impl Not for $name {
-            type Output = Self;
+        })*
+    }
+}
 
-            #[inline(always)]
-            fn not(self) -> Self {
-              $name($(!self.$index),*)
-            }
-       })*
-    }
-}
-
-bool_impls! {
-    bool32x2:i32, 2,    0:x0, 1:x1;
-    bool32x4:i32, 4,    0:x0, 1:x1 , 2:x2, 3:x3;    
-    bool16x8:i16, 8,    0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-                    
-    bool8x16:i8, 16,    0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                        8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                    
-    bool64x2:i64, 2,    0:x0, 1:x1;
-    bool64x4:i64, 4,    0:x0, 1:x1 , 2:x2, 3:x3;
-    bool32x8:i32, 8,    0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    
-    bool16x16:i16, 16,  0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                        8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15;
-                    
-    bool8x32:i8, 32,    0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7,
-                        8:x8, 9:x9 , 10:x10, 11:x11, 12:x12, 13:x13 ,14:x14, 15:x15,
-                        16:x16, 17:x17 , 18:x18, 19:x19, 20:x20, 21:x21 ,22:x22, 23:x23,
-                        24:x24, 25:x25 , 26:x26, 27:x27, 28:x28, 29:x29 ,30:x30, 31:x31;
-}
-
-macro_rules! float_impls {
+// Rounding conversion from floats
+round_conv_impls! {
+    to_i_round, round, f32 -> i32;
+    to_i_floor, floor, f32 -> i32;
+    to_i_ceil, ceil, f32 -> i32;
+    to_u_round, round, f32 -> u32;
+    to_u_floor, floor, f32 -> u32;
+    to_u_ceil, ceil, f32 -> u32;
+
+    to_i_round, round, f64 -> i64;
+    to_i_floor, floor, f64 -> i64;
+    to_i_ceil, ceil, f64 -> i64;
+    to_u_round, round, f64 -> u64;
+    to_u_floor, floor, f64 -> u64;
+    to_u_ceil, ceil, f64 -> u64;
+}
+
+macro_rules! half_conv_impls {
     ($(
-        $name: ident, 
-        $($index:tt : $field:ident),*;
+        $cvt: ident, $half: expr, $from_name: ident -> $to_name: ident : $to_elem: ident, $offset: expr;
         )*) => {
-        
-        $(impl $name {
-            /// Get square root
-            #[inline]
-            pub fn sqrt(self) -> Self {
-                $name($(self.$index.sqrt()),*)
-            }
 
-            /// Get reciprocal of square root
-            #[inline]
-            pub fn approx_rsqrt(self) -> Self {
-                $name($(1.0 / self.$index.sqrt()),*)
+        $(impl $from_name {
+            #[doc = concat!("Convert the ", $half, " half of the lanes to the wider element type.")]
+            /// Each lane keeps its value: unsigned lanes are zero-extended and
+            /// signed lanes are sign-extended.
+            #[inline(always)]
+            pub fn $cvt(self) -> $to_name {
+                $to_name::from_lanes(array::from_fn(|i| self.0[i + $offset] as $to_elem))
             }
-
-            /// Get reciprocal
-            #[inline]
-            pub fn approx_reciprocal(self) -> Self {
-                $name($(1.0 / self.$index),*)
-            }                
-       })*
+        })*
     }
 }
 
-float_impls! {
-    f32x2, 0:x0, 1:x1;
-    f32x4, 0:x0, 1:x1 , 2:x2, 3:x3;
-    f32x8, 0:x0, 1:x1 , 2:x2, 3:x3, 4:x4, 5:x5 , 6:x6, 7:x7;    
-    f64x2, 0:x0, 1:x1;
-    f64x4, 0:x0, 1:x1 , 2:x2, 3:x3;
+// Widening conversion of the lower or upper half of the lanes
+half_conv_impls! {
+    to_u16_lo, "lower", u8x16 -> u16x8 : u16, 0;
+    to_u16_hi, "upper", u8x16 -> u16x8 : u16, 8;
+    to_i16_lo, "lower", i8x16 -> i16x8 : i16, 0;
+    to_i16_hi, "upper", i8x16 -> i16x8 : i16, 8;
 }
 
-macro_rules! conv_impls {
+macro_rules! pack_impls {
     ($(
-        $cvt: ident,
-        $from_name: ident : $from_elem: ident -> $to_name : ident : $to_elem : ident,
-        $($index:tt : $field:ident),*;
+        $cvt: ident, $from_name: ident -> $to_name: ident : $to_elem: ident, $half: expr;
         )*) => {
-            
-        $(impl $from_name {
-            pub fn $cvt(self) -> $to_name {
-                $to_name($(self.$index as $to_elem), *)
+
+        $(impl $to_name {
+            /// Create new instance from the lanes of `lo` followed by the lanes of `hi`,
+            /// truncating each lane to the narrower element type
+            #[inline(always)]
+            pub fn $cvt(lo: $from_name, hi: $from_name) -> $to_name {
+                $to_name::from_lanes(array::from_fn(|i| if i < $half { lo.0[i] } else { hi.0[i - $half] } as $to_elem))
             }
         })*
     }
 }
 
-/// Conversion among types
-conv_impls! {
-    to_i, u32x2 : u32 -> i32x2 : i32,       0:x0, 1:x1;
-    to_i, f32x2 : f32 -> i32x2 : i32,       0:x0, 1:x1;    
-    to_u, i32x2 : i32 -> u32x2 : u32,       0:x0, 1:x1;
-    to_u, f32x2 : f32 -> u32x2 : u32,       0:x0, 1:x1;    
-    to_f, u32x2 : u32 -> f32x2 : f32,       0:x0, 1:x1;
-    to_f, i32x2 : i32 -> f32x2 : f32,       0:x0, 1:x1;
-    to_i, bool32x2 : i32 -> i32x2 : i32,    0:x0, 1:x1;
-    to_u, bool32x2 : i32 -> u32x2 : u32,    0:x0, 1:x1;
-    
-    to_i, u32x4 : u32 -> i32x4 : i32,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_i, f32x4 : f32 -> i32x4 : i32,       0:x0, 1:x1, 2:x2, 3:x3;    
-    to_u, i32x4 : i32 -> u32x4 : u32,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_u, f32x4 : f32 -> u32x4 : u32,       0:x0, 1:x1, 2:x2, 3:x3;    
-    to_f, u32x4 : u32 -> f32x4 : f32,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_f, i32x4 : i32 -> f32x4 : f32,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_i, bool32x4 : i32 -> i32x4 : i32,    0:x0, 1:x1, 2:x2, 3:x3;
-    to_u, bool32x4 : i32 -> u32x4 : u32,    0:x0, 1:x1, 2:x2, 3:x3;
-    
-    to_i, u32x8 : u32 -> i32x8 : i32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    to_i, f32x8 : f32 -> i32x8 : i32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;    
-    to_u, i32x8 : i32 -> u32x8 : u32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    to_u, f32x8 : f32 -> u32x8 : u32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;    
-    to_f, u32x8 : u32 -> f32x8 : f32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    to_f, i32x8 : i32 -> f32x8 : f32,       0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    to_i, bool32x8 : i32 -> i32x8 : i32,    0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    to_u, bool32x8 : i32 -> u32x8 : u32,    0:x0, 1:x1, 2:x2, 3:x3, 4:x4, 5:x5, 6:x6, 7:x7;
-    
-    to_i, u64x2 : u64 -> i64x2 : i64,       0:x0, 1:x1;
-    to_i, f64x2 : f64 -> i64x2 : i64,       0:x0, 1:x1;    
-    to_u, i64x2 : i64 -> u64x2 : u64,       0:x0, 1:x1;
-    to_u, f64x2 : f64 -> u64x2 : u64,       0:x0, 1:x1;
-    to_f, u64x2 : u64 -> f64x2 : f64,       0:x0, 1:x1;
-    to_f, i64x2 : i64 -> f64x2 : f64,       0:x0, 1:x1;
-    to_i, bool64x2 : i64 -> i64x2 : i64,    0:x0, 1:x1;
-    to_u, bool64x2 : i64 -> u64x2 : u64,    0:x0, 1:x1;
-    
-    to_i, u64x4 : u64 -> i64x4 : i64,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_i, f64x4 : f64 -> i64x4 : i64,       0:x0, 1:x1, 2:x2, 3:x3;    
-    to_u, i64x4 : i64 -> u64x4 : u64,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_u, f64x4 : f64 -> u64x4 : u64,       0:x0, 1:x1, 2:x2, 3:x3;    
-    to_f, u64x4 : u64 -> f64x4 : f64,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_f, i64x4 : i64 -> f64x4 : f64,       0:x0, 1:x1, 2:x2, 3:x3;
-    to_i, bool64x4 : i64 -> i64x4 : i64,    0:x0, 1:x1, 2:x2, 3:x3;
-    to_u, bool64x4 : i64 -> u64x4 : u64,    0:x0, 1:x1, 2:x2, 3:x3;
-    
-    to_i32, i64x2 : i64 -> i32x2 : i32,     0:x0, 1:x1;
-    to_i64, i32x2 : i32 -> i64x2 : i64,     0:x0, 1:x1;    
-    to_i32, i64x4 : i64 -> i32x4 : i32,     0:x0, 1:x1, 2:x2, 3:x3;
-    to_i64, i32x4 : i32 -> i64x4 : i64,     0:x0, 1:x1, 2:x2, 3:x3;
-    
-    to_u32, u64x2 : u64 -> u32x2 : u32,     0:x0, 1:x1;
-    to_u64, u32x2 : u32 -> u64x2 : u64,     0:x0, 1:x1;    
-    to_u32, u64x4 : u64 -> u32x4 : u32,     0:x0, 1:x1, 2:x2, 3:x3;
-    to_u64, u32x4 : u32 -> u64x4 : u64,     0:x0, 1:x1, 2:x2, 3:x3;
-    
-    to_f32, f64x2 : f64 -> f32x2 : f32,     0:x0, 1:x1;
-    to_f64, f32x2 : f32 -> f64x2 : f64,     0:x0, 1:x1;    
-    to_f32, f64x4 : f64 -> f32x4 : f32,     0:x0, 1:x1, 2:x2, 3:x3;
-    to_f64, f32x4 : f32 -> f64x4 : f64,     0:x0, 1:x1, 2:x2, 3:x3;
+// Narrowing conversion of two vectors into one
+pack_impls! {
+    from_u16, u16x8 -> u8x16 : u8, 8;
+    from_i16, i16x8 -> i8x16 : i8, 8;
 }
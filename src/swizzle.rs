@@ -0,0 +1,399 @@
+// Lane rearrangements with indices known at compile time. Each output lane is
+// a copy of one input lane, so after inlining LLVM sees a single
+// `shufflevector` and lowers it to `shufps`, `pshufd`, `vpermps` and friends.
+
+use std::array;
+use std::marker::PhantomData;
+
+use {Simd, Mask, LaneCount, SupportedLaneCount, SimdElement, MaskElement};
+
+/// Compile-time lane indices for `swizzle` and `shuffle`, usually implemented
+/// by the `swizzle!` and `shuffle!` macros.
+///
+/// Output lane `i` is input lane `INDEX[i]`. For `shuffle`, the input lanes
+/// are the lanes of the first vector followed by the lanes of the second.
+pub trait Shuffle<const M: usize> {
+    /// Index of the input lane copied to each output lane
+    const INDEX: [usize; M];
+}
+
+const fn indices_in_range(index: &[usize], lanes: usize) -> bool {
+    let mut i = 0;
+    while i < index.len() {
+        if index[i] >= lanes {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Checks that the indices of `S` are below `K * N`. `swizzle` and `shuffle`
+// read `OK`, so it is evaluated when they are instantiated, and an index out of
+// range is a build error instead of a panic. As with any error that depends on
+// the generic parameters, `cargo build` reports it but `cargo check` may not.
+struct InRange<S, const M: usize, const N: usize, const K: usize>(PhantomData<S>);
+
+impl<S: Shuffle<M>, const M: usize, const N: usize, const K: usize> InRange<S, M, N, K> {
+    const OK: () = assert!(indices_in_range(&S::INDEX, K * N), "lane index out of range");
+}
+
+impl<T: SimdElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Rearrange the lanes of `self`: output lane `i` is lane `S::INDEX[i]`.
+    /// See the `swizzle!` macro.
+    #[inline(always)]
+    pub fn swizzle<S: Shuffle<M>, const M: usize>(self) -> Simd<T, M> where LaneCount<M>: SupportedLaneCount {
+        let () = InRange::<S, M, N, 1>::OK;
+        Simd::from_lanes(array::from_fn(|i| self.0[S::INDEX[i]]))
+    }
+
+    /// Pick lanes from `self` and `other`: output lane `i` is lane
+    /// `S::INDEX[i]` of `self` if it is less than `N`, else lane
+    /// `S::INDEX[i] - N` of `other`. See the `shuffle!` macro.
+    #[inline(always)]
+    pub fn shuffle<S: Shuffle<M>, const M: usize>(self, other: Self) -> Simd<T, M>
+        where LaneCount<M>: SupportedLaneCount {
+        let () = InRange::<S, M, N, 2>::OK;
+        Simd::from_lanes(array::from_fn(|i| {
+            let j = S::INDEX[i];
+            if j < N { self.0[j] } else { other.0[j - N] }
+        }))
+    }
+}
+
+impl<T: MaskElement, const N: usize> Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Rearrange the lanes of `self`, like `Simd::swizzle`
+    #[inline(always)]
+    pub fn swizzle<S: Shuffle<M>, const M: usize>(self) -> Mask<T, M> where LaneCount<M>: SupportedLaneCount {
+        Mask::from_lanes(self.to_i().swizzle::<S, M>().0)
+    }
+
+    /// Pick lanes from `self` and `other`, like `Simd::shuffle`
+    #[inline(always)]
+    pub fn shuffle<S: Shuffle<M>, const M: usize>(self, other: Self) -> Mask<T, M>
+        where LaneCount<M>: SupportedLaneCount {
+        Mask::from_lanes(self.to_i().shuffle::<S, M>(other.to_i()).0)
+    }
+}
+
+// Named lane permutations, implemented as shuffles whose indices are computed
+// for each lane count
+#[derive(Clone, Copy)]
+enum Permutation {
+    Reverse,
+    RotateLeft(usize),
+    RotateRight(usize),
+    Broadcast(usize),
+    InterleaveLo,
+    InterleaveHi,
+    Evens,
+    Odds,
+    Offset(usize),
+}
+
+const fn permutation<const N: usize>(p: Permutation) -> [usize; N] {
+    let mut index = [0; N];
+    let mut i = 0;
+    while i < N {
+        index[i] = match p {
+            Permutation::Reverse => N - 1 - i,
+            Permutation::RotateLeft(k) => (i + k % N) % N,
+            Permutation::RotateRight(k) => (i + N - k % N) % N,
+            Permutation::Broadcast(k) => k,
+            Permutation::InterleaveLo => i / 2 + (i % 2) * N,
+            Permutation::InterleaveHi => N / 2 + i / 2 + (i % 2) * N,
+            Permutation::Evens => 2 * i,
+            Permutation::Odds => 2 * i + 1,
+            Permutation::Offset(k) => i + k,
+        };
+        i += 1;
+    }
+    index
+}
+
+struct Reverse;
+struct RotateLeft<const K: usize>;
+struct RotateRight<const K: usize>;
+struct Broadcast<const I: usize>;
+struct InterleaveLo;
+struct InterleaveHi;
+struct Evens;
+struct Odds;
+struct Offset<const K: usize>;
+
+impl<const N: usize> Shuffle<N> for Reverse {
+    const INDEX: [usize; N] = permutation(Permutation::Reverse);
+}
+
+impl<const K: usize, const N: usize> Shuffle<N> for RotateLeft<K> {
+    const INDEX: [usize; N] = permutation(Permutation::RotateLeft(K));
+}
+
+impl<const K: usize, const N: usize> Shuffle<N> for RotateRight<K> {
+    const INDEX: [usize; N] = permutation(Permutation::RotateRight(K));
+}
+
+impl<const I: usize, const N: usize> Shuffle<N> for Broadcast<I> {
+    const INDEX: [usize; N] = permutation(Permutation::Broadcast(I));
+}
+
+impl<const N: usize> Shuffle<N> for InterleaveLo {
+    const INDEX: [usize; N] = permutation(Permutation::InterleaveLo);
+}
+
+impl<const N: usize> Shuffle<N> for InterleaveHi {
+    const INDEX: [usize; N] = permutation(Permutation::InterleaveHi);
+}
+
+impl<const N: usize> Shuffle<N> for Evens {
+    const INDEX: [usize; N] = permutation(Permutation::Evens);
+}
+
+impl<const N: usize> Shuffle<N> for Odds {
+    const INDEX: [usize; N] = permutation(Permutation::Odds);
+}
+
+impl<const K: usize, const N: usize> Shuffle<N> for Offset<K> {
+    const INDEX: [usize; N] = permutation(Permutation::Offset(K));
+}
+
+impl<T: SimdElement, const N: usize> Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    /// Reverse the order of the lanes
+    #[inline(always)]
+    pub fn reverse(self) -> Self {
+        self.swizzle::<Reverse, N>()
+    }
+
+    /// Rotate the lanes left by `K`: lane `i` of the result is lane
+    /// `(i + K) % N` of `self`
+    #[inline(always)]
+    pub fn rotate_lanes_left<const K: usize>(self) -> Self {
+        self.swizzle::<RotateLeft<K>, N>()
+    }
+
+    /// Rotate the lanes right by `K`: lane `(i + K) % N` of the result is
+    /// lane `i` of `self`
+    #[inline(always)]
+    pub fn rotate_lanes_right<const K: usize>(self) -> Self {
+        self.swizzle::<RotateRight<K>, N>()
+    }
+
+    /// Set all lanes to lane `I`.
+    /// An `I` out of range is a compile error.
+    #[inline(always)]
+    pub fn broadcast<const I: usize>(self) -> Self {
+        self.swizzle::<Broadcast<I>, N>()
+    }
+
+    /// Interleave the lanes of `self` and `other`, returning the low half
+    /// `[a0, b0, a1, b1, ...]` and the high half `[.., an-1, bn-1]`
+    #[inline(always)]
+    pub fn interleave(self, other: Self) -> (Self, Self) {
+        (self.shuffle::<InterleaveLo, N>(other), self.shuffle::<InterleaveHi, N>(other))
+    }
+
+    /// Split the lanes of `self` followed by `other` into the even lanes
+    /// and the odd lanes. This is the inverse of `interleave`.
+    #[inline(always)]
+    pub fn deinterleave(self, other: Self) -> (Self, Self) {
+        (self.shuffle::<Evens, N>(other), self.shuffle::<Odds, N>(other))
+    }
+}
+
+// The half lane count can't be computed from a generic `N` on stable Rust, so
+// splitting and concatenating are implemented for each lane count
+macro_rules! split_impls {
+    ($($lanes: expr => $half: expr),*) => {
+        $(impl<T: SimdElement> Simd<T, $lanes> {
+            /// Get the low half of the lanes
+            #[inline(always)]
+            pub fn low(self) -> Simd<T, $half> {
+                self.swizzle::<Offset<0>, $half>()
+            }
+
+            /// Get the high half of the lanes
+            #[inline(always)]
+            pub fn high(self) -> Simd<T, $half> {
+                self.swizzle::<Offset<$half>, $half>()
+            }
+
+            /// Split into the low half and the high half of the lanes
+            #[inline(always)]
+            pub fn split(self) -> (Simd<T, $half>, Simd<T, $half>) {
+                (self.low(), self.high())
+            }
+
+            /// Concatenate two vectors: the lanes of `lo` followed by the lanes of `hi`
+            #[inline(always)]
+            pub fn from_halves(lo: Simd<T, $half>, hi: Simd<T, $half>) -> Self {
+                lo.shuffle::<Offset<0>, $lanes>(hi)
+            }
+        }
+
+        impl<T: MaskElement> Mask<T, $lanes> {
+            /// Get the low half of the lanes
+            #[inline(always)]
+            pub fn low(self) -> Mask<T, $half> {
+                self.swizzle::<Offset<0>, $half>()
+            }
+
+            /// Get the high half of the lanes
+            #[inline(always)]
+            pub fn high(self) -> Mask<T, $half> {
+                self.swizzle::<Offset<$half>, $half>()
+            }
+
+            /// Split into the low half and the high half of the lanes
+            #[inline(always)]
+            pub fn split(self) -> (Mask<T, $half>, Mask<T, $half>) {
+                (self.low(), self.high())
+            }
+
+            /// Concatenate two boolean vectors: the lanes of `lo` followed by the lanes of `hi`
+            #[inline(always)]
+            pub fn from_halves(lo: Mask<T, $half>, hi: Mask<T, $half>) -> Self {
+                lo.shuffle::<Offset<0>, $lanes>(hi)
+            }
+        })*
+    }
+}
+
+split_impls! { 4 => 2, 8 => 4, 16 => 8, 32 => 16, 64 => 32 }
+
+// Byte table lookups with indices known only at run time. LLVM does not turn
+// a lane-by-lane lookup into `pshufb`, so it is called directly when the build
+// enables SSSE3, for example with `-C target-cpu=native`. The other forms are
+// built on it.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3"))]
+#[inline(always)]
+fn pshufb(table: [u8; 16], indices: [u8; 16]) -> [u8; 16] {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::{__m128i, _mm_loadu_si128, _mm_shuffle_epi8, _mm_storeu_si128};
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_shuffle_epi8, _mm_storeu_si128};
+
+    let mut out = [0; 16];
+    // SAFETY: SSSE3 is enabled at compile time, and the loads and stores are
+    // unaligned accesses to 16-byte arrays
+    unsafe {
+        let t = _mm_loadu_si128(table.as_ptr() as *const __m128i);
+        let i = _mm_loadu_si128(indices.as_ptr() as *const __m128i);
+        _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, _mm_shuffle_epi8(t, i));
+    }
+    out
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "ssse3")))]
+#[inline(always)]
+fn pshufb(table: [u8; 16], indices: [u8; 16]) -> [u8; 16] {
+    array::from_fn(|i| {
+        let j = indices[i];
+        if j & 0x80 != 0 { 0 } else { table[(j & 0x0f) as usize] }
+    })
+}
+
+impl Simd<u8, 16> {
+    /// Look up each lane of `indices` in the 16-entry `self`, like `pshufb`:
+    /// lane `i` is `self[indices[i] & 15]`, or zero if bit 7 of `indices[i]` is set
+    #[inline(always)]
+    pub fn lookup(self, indices: Self) -> Self {
+        Simd::from_lanes(pshufb(self.0, indices.0))
+    }
+
+    /// Look up each lane of `indices` in the 16-entry `self`: lane `i` is
+    /// `self[indices[i]]`, or zero if `indices[i]` is 16 or more
+    #[inline(always)]
+    pub fn lookup_or_zero(self, indices: Self) -> Self {
+        // Adding 0x70 with saturation keeps the low 4 bits of indices below
+        // 16 and sets bit 7 of all the others
+        self.lookup(indices.saturating_add(Simd::splat(0x70)))
+    }
+}
+
+// Apply `f` to the 128-bit halves of two 32-byte vectors
+#[inline(always)]
+fn by_halves(a: Simd<u8, 32>, b: Simd<u8, 32>, f: impl Fn(Simd<u8, 16>, Simd<u8, 16>) -> Simd<u8, 16>) -> Simd<u8, 32> {
+    let mut out = [0; 32];
+    for k in 0..2 {
+        let half = |v: Simd<u8, 32>| Simd::<u8, 16>::load(&v.0, 16 * k);
+        out[16 * k..16 * k + 16].copy_from_slice(&f(half(a), half(b)).0);
+    }
+    Simd::from_lanes(out)
+}
+
+impl Simd<u8, 32> {
+    /// Look up each lane of `indices` in the 16-entry table in the same
+    /// 128-bit half of `self`, like the AVX2 `vpshufb`. Each half behaves as
+    /// `u8x16::lookup`.
+    #[inline(always)]
+    pub fn lookup(self, indices: Self) -> Self {
+        by_halves(self, indices, |table, i| table.lookup(i))
+    }
+
+    /// Look up each lane of `indices` in the 32-entry `self`, across the
+    /// halves: lane `i` is `self[indices[i]]`, or zero if `indices[i]` is 32 or more
+    #[inline(always)]
+    pub fn lookup_wide(self, indices: Self) -> Self {
+        let (lo, hi) = (Simd::<u8, 16>::load(&self.0, 0), Simd::<u8, 16>::load(&self.0, 16));
+        // Indices below 16 wrap past 16 when 16 is subtracted, so each lane
+        // is found in at most one of the two halves of the table
+        by_halves(indices, indices, |i, _| lo.lookup_or_zero(i) | hi.lookup_or_zero(i - Simd::splat(16)))
+    }
+}
+
+/// Rearrange the lanes of a vector with indices known at compile time.
+/// `swizzle!(v, [3, 2, 1, 0])` reverses a 4-lane vector, and
+/// `swizzle!(v, [0, 0, 0, 0])` broadcasts its first lane.
+/// The number of indices is the number of lanes of the result.
+/// An index out of range is a compile error:
+///
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate ssimd;
+/// use ssimd::f32x4;
+///
+/// fn main() {
+///     let v = f32x4::new(1.0, 2.0, 3.0, 4.0);
+///     let _ = swizzle!(v, [4, 0, 0, 0]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! swizzle {
+    ($v: expr, [$($index: expr),+ $(,)*]) => {{
+        const LANES: usize = [$($index),+].len();
+        struct Indices;
+        impl $crate::Shuffle<LANES> for Indices {
+            const INDEX: [usize; LANES] = [$($index),+];
+        }
+        ($v).swizzle::<Indices, LANES>()
+    }}
+}
+
+/// Pick lanes from two vectors of `N` lanes with indices known at compile
+/// time: indices `0..N` are the lanes of the first vector and `N..2 * N` are
+/// the lanes of the second. `shuffle!(a, b, [0, 4, 1, 5])` interleaves the low
+/// halves of two 4-lane vectors.
+/// The number of indices is the number of lanes of the result.
+/// An index out of range is a compile error:
+///
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate ssimd;
+/// use ssimd::f32x4;
+///
+/// fn main() {
+///     let (a, b) = (f32x4::splat(1.0), f32x4::splat(2.0));
+///     let _ = shuffle!(a, b, [8, 0, 1, 2]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! shuffle {
+    ($a: expr, $b: expr, [$($index: expr),+ $(,)*]) => {{
+        const LANES: usize = [$($index),+].len();
+        struct Indices;
+        impl $crate::Shuffle<LANES> for Indices {
+            const INDEX: [usize; LANES] = [$($index),+];
+        }
+        ($a).shuffle::<Indices, LANES>($b)
+    }}
+}
@@ -0,0 +1,423 @@
+// Traits implemented by every vector and boolean vector type, so kernels can
+// be written once and called with the width the caller picks, e.g.
+// `fn axpy<V: SimdFloat<Elem = f32>>(...)`.
+//
+// The methods forward to the inherent methods of `Simd` and `Mask`, which are
+// documented in lib.rs and math.rs.
+//
+// The traits are sealed with the `Sealed` trait of element.rs, so methods can
+// be added to them without breaking other crates. `SimdFloat` and `SimdInt`
+// are sealed through `SimdVector`.
+
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, ShlAssign, ShrAssign};
+
+use {Simd, Mask, Select, LaneCount, SupportedLaneCount, SimdElement, IntElement, MaskElement};
+use element::Sealed;
+
+/// Operations shared by all vector types. This trait is sealed: it is only
+/// implemented by `Simd`.
+pub trait SimdVector: Sealed + Copy + Debug
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    + AddAssign + SubAssign + MulAssign + DivAssign {
+    /// Lane type
+    type Elem: SimdElement;
+    /// Boolean vector returned by comparisons
+    type Mask: SimdMask + Select<Self>;
+    /// Number of lanes
+    const LANES: usize;
+
+    /// Create new instance with all lanes set to a value
+    fn splat(x: Self::Elem) -> Self;
+    /// Load instance from an array
+    fn load(array: &[Self::Elem], idx: usize) -> Self;
+    /// Store self to an array
+    fn store(self, array: &mut [Self::Elem], idx: usize);
+    /// Load up to `LANES` lanes from an array, filling the lanes past its end
+    fn load_partial(array: &[Self::Elem], idx: usize, fill: Self::Elem) -> Self;
+    /// Store up to `LANES` lanes to an array, dropping the lanes past its end
+    fn store_partial(self, array: &mut [Self::Elem], idx: usize);
+    /// Load the lanes where `mask` is true, take the others from `or`
+    fn load_masked(array: &[Self::Elem], idx: usize, mask: Self::Mask, or: Self) -> Self;
+    /// Store the lanes where `mask` is true
+    fn store_masked(self, array: &mut [Self::Elem], idx: usize, mask: Self::Mask);
+    /// Set `dst` to `f` applied to `src`, one vector at a time, including the tail
+    fn map_slice(src: &[Self::Elem], dst: &mut [Self::Elem], f: impl FnMut(Self) -> Self);
+    /// Set `dst` to `f` applied to `a` and `b`, one pair of vectors at a time, including the tail
+    fn zip_map(a: &[Self::Elem], b: &[Self::Elem], dst: &mut [Self::Elem], f: impl FnMut(Self, Self) -> Self);
+    /// Get the `idx`th lane value
+    fn extract(self, idx: u32) -> Self::Elem;
+    /// Replace the `idx`th lane with new value
+    fn replace(self, idx: u32, elem: Self::Elem) -> Self;
+
+    /// Compare if equal
+    fn eq(self, rhs: Self) -> Self::Mask;
+    /// Compare if not equal
+    fn ne(self, rhs: Self) -> Self::Mask;
+    /// Compare if less than
+    fn lt(self, rhs: Self) -> Self::Mask;
+    /// Compare if less than or equal
+    fn le(self, rhs: Self) -> Self::Mask;
+    /// Compare if greater than
+    fn gt(self, rhs: Self) -> Self::Mask;
+    /// Compare if greater than or equal
+    fn ge(self, rhs: Self) -> Self::Mask;
+    /// Get max values by lane
+    fn max(self, rhs: Self) -> Self;
+    /// Get min values by lane
+    fn min(self, rhs: Self) -> Self;
+
+    /// Get the sum of all lanes
+    fn sum(self) -> Self::Elem;
+    /// Get the product of all lanes
+    fn product(self) -> Self::Elem;
+    /// Get the smallest lane value
+    fn min_element(self) -> Self::Elem;
+    /// Get the largest lane value
+    fn max_element(self) -> Self::Elem;
+
+    /// Reverse the order of the lanes
+    fn reverse(self) -> Self;
+    /// Rotate the lanes left by `K`
+    fn rotate_lanes_left<const K: usize>(self) -> Self;
+    /// Rotate the lanes right by `K`
+    fn rotate_lanes_right<const K: usize>(self) -> Self;
+    /// Set all lanes to lane `I`
+    fn broadcast<const I: usize>(self) -> Self;
+    /// Interleave the lanes of `self` and `other` into a low and a high half
+    fn interleave(self, other: Self) -> (Self, Self);
+    /// Split the lanes of `self` followed by `other` into the even and the odd lanes
+    fn deinterleave(self, other: Self) -> (Self, Self);
+
+    /// Add `rhs` to the lanes where `mask` is true, keep the other lanes of `self`
+    fn add_masked(self, rhs: Self, mask: Self::Mask) -> Self;
+    /// Subtract `rhs` from the lanes where `mask` is true, keep the other lanes of `self`
+    fn sub_masked(self, rhs: Self, mask: Self::Mask) -> Self;
+    /// Multiply by `rhs` the lanes where `mask` is true, keep the other lanes of `self`
+    fn mul_masked(self, rhs: Self, mask: Self::Mask) -> Self;
+    /// Divide by `rhs` the lanes where `mask` is true, keep the other lanes of `self`
+    fn div_masked(self, rhs: Self, mask: Self::Mask) -> Self;
+}
+
+/// Operations of the float vector types
+pub trait SimdFloat: SimdVector + Neg<Output = Self> {
+    /// Get square root
+    fn sqrt(self) -> Self;
+    /// Get reciprocal of square root
+    fn approx_rsqrt(self) -> Self;
+    /// Get reciprocal
+    fn approx_reciprocal(self) -> Self;
+    /// Compute `self * a + b` with a single rounding
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    /// Compute `self * a - b` with a single rounding
+    fn mul_sub(self, a: Self, b: Self) -> Self;
+    /// Compute `-(self * a) + b` with a single rounding
+    fn neg_mul_add(self, a: Self, b: Self) -> Self;
+    /// Compute `-(self * a) - b` with a single rounding
+    fn neg_mul_sub(self, a: Self, b: Self) -> Self;
+    /// Round each lane toward negative infinity
+    fn floor(self) -> Self;
+    /// Round each lane toward positive infinity
+    fn ceil(self) -> Self;
+    /// Round each lane to the nearest integer, ties away from zero
+    fn round(self) -> Self;
+    /// Round each lane to the nearest integer, ties to even
+    fn round_ties_even(self) -> Self;
+    /// Round each lane toward zero
+    fn trunc(self) -> Self;
+    /// Get the fractional part of each lane, `self - self.trunc()`
+    fn fract(self) -> Self;
+    /// Get the sum of all lanes, added strictly from left to right
+    fn ordered_sum(self) -> Self::Elem;
+
+    /// Get `e^x` of each lane
+    fn exp(self) -> Self;
+    /// Get `2^x` of each lane
+    fn exp2(self) -> Self;
+    /// Get the natural logarithm of each lane
+    fn ln(self) -> Self;
+    /// Get the base 2 logarithm of each lane
+    fn log2(self) -> Self;
+    /// Get the base 10 logarithm of each lane
+    fn log10(self) -> Self;
+    /// Get the sine and the cosine of each lane
+    fn sin_cos(self) -> (Self, Self);
+    /// Get the sine of each lane
+    fn sin(self) -> Self;
+    /// Get the cosine of each lane
+    fn cos(self) -> Self;
+    /// Get the tangent of each lane
+    fn tan(self) -> Self;
+    /// Get the arctangent of each lane
+    fn atan(self) -> Self;
+    /// Get the four quadrant arctangent of `self` (y) and `x`
+    fn atan2(self, x: Self) -> Self;
+    /// Get the arcsine of each lane
+    fn asin(self) -> Self;
+    /// Get the arccosine of each lane
+    fn acos(self) -> Self;
+    /// Get the hyperbolic tangent of each lane
+    fn tanh(self) -> Self;
+    /// Raise each lane of `self` to the power of the lane of `n`
+    fn powf(self, n: Self) -> Self;
+    /// Get the cube root of each lane
+    fn cbrt(self) -> Self;
+}
+
+/// Operations of the integer vector types
+pub trait SimdInt: SimdVector + Rem<Output = Self> + RemAssign
+    + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> + Not<Output = Self>
+    + BitAndAssign + BitOrAssign + BitXorAssign
+    + Shl<usize, Output = Self> + Shr<usize, Output = Self> + ShlAssign<usize> + ShrAssign<usize>
+    + Shl<Output = Self> + Shr<Output = Self> + ShlAssign + ShrAssign {
+    /// Add with wrapping on overflow, same as `+`
+    fn wrapping_add(self, rhs: Self) -> Self;
+    /// Subtract with wrapping on overflow, same as `-`
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    /// Multiply with wrapping on overflow, same as `*`
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    /// Add, clamping each lane to the bounds of the element type
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Subtract, clamping each lane to the bounds of the element type
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Shift each lane left by `n` modulo the lane width
+    fn wrapping_shl(self, n: u32) -> Self;
+    /// Shift each lane right by `n` modulo the lane width
+    fn wrapping_shr(self, n: u32) -> Self;
+    /// Rotate the bits of each lane left by `n`
+    fn rotate_left(self, n: u32) -> Self;
+    /// Rotate the bits of each lane right by `n`
+    fn rotate_right(self, n: u32) -> Self;
+    /// Rotate the bits of each lane left by the corresponding lane of `n`
+    fn rotate_left_by(self, n: Self) -> Self;
+    /// Rotate the bits of each lane right by the corresponding lane of `n`
+    fn rotate_right_by(self, n: Self) -> Self;
+    /// Get the bitwise and of all lanes
+    fn and(self) -> Self::Elem;
+    /// Get the bitwise or of all lanes
+    fn or(self) -> Self::Elem;
+    /// Get the bitwise xor of all lanes
+    fn xor(self) -> Self::Elem;
+}
+
+/// Operations of the boolean vector types. This trait is sealed: it is only
+/// implemented by `Mask`.
+pub trait SimdMask: Sealed + Copy + Debug
+    + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> + Not<Output = Self>
+    + BitAndAssign + BitOrAssign + BitXorAssign {
+    /// Signed integer vector with the same lane width, returned by `to_i`
+    type Int: SimdInt;
+    /// Number of lanes
+    const LANES: usize;
+
+    /// Create new instance with all lanes set to a value
+    fn splat(x: bool) -> Self;
+    /// Load instance from an array
+    fn load(array: &[bool], idx: usize) -> Self;
+    /// Store self to an array
+    fn store(self, array: &mut [bool], idx: usize);
+    /// Get the `idx`th lane value
+    fn extract(self, idx: u32) -> bool;
+    /// Replace the `idx`th lane with new value
+    fn replace(self, idx: u32, elem: bool) -> Self;
+    /// Check if all lanes are true
+    fn all(self) -> bool;
+    /// Check if any lane is true
+    fn any(self) -> bool;
+    /// Get the lanes as signed integers: -1 for true and 0 for false
+    fn to_i(self) -> Self::Int;
+}
+
+// Forward trait methods taking `self` to the inherent methods of the same name
+macro_rules! forward {
+    ($(fn $name: ident($($arg: ident: $ty: ty),*) -> $ret: ty;)*) => {
+        $(#[inline(always)]
+        fn $name(self, $($arg: $ty),*) -> $ret {
+            self.$name($($arg),*)
+        })*
+    }
+}
+
+impl<T: SimdElement, const N: usize> Sealed for Simd<T, N> where LaneCount<N>: SupportedLaneCount {}
+
+impl<T: SimdElement, const N: usize> SimdVector for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Elem = T;
+    type Mask = Mask<T::Mask, N>;
+    const LANES: usize = N;
+
+    #[inline(always)]
+    fn splat(x: T) -> Self {
+        Simd::splat(x)
+    }
+
+    #[inline(always)]
+    fn load(array: &[T], idx: usize) -> Self {
+        Simd::load(array, idx)
+    }
+
+    #[inline(always)]
+    fn store(self, array: &mut [T], idx: usize) {
+        Simd::store(self, array, idx)
+    }
+
+    #[inline(always)]
+    fn load_partial(array: &[T], idx: usize, fill: T) -> Self {
+        Simd::load_partial(array, idx, fill)
+    }
+
+    #[inline(always)]
+    fn store_partial(self, array: &mut [T], idx: usize) {
+        Simd::store_partial(self, array, idx)
+    }
+
+    #[inline(always)]
+    fn load_masked(array: &[T], idx: usize, mask: Self::Mask, or: Self) -> Self {
+        Simd::load_masked(array, idx, mask, or)
+    }
+
+    #[inline(always)]
+    fn store_masked(self, array: &mut [T], idx: usize, mask: Self::Mask) {
+        Simd::store_masked(self, array, idx, mask)
+    }
+
+    #[inline(always)]
+    fn map_slice(src: &[T], dst: &mut [T], f: impl FnMut(Self) -> Self) {
+        Simd::map_slice(src, dst, f)
+    }
+
+    #[inline(always)]
+    fn zip_map(a: &[T], b: &[T], dst: &mut [T], f: impl FnMut(Self, Self) -> Self) {
+        Simd::zip_map(a, b, dst, f)
+    }
+
+    #[inline(always)]
+    fn rotate_lanes_left<const K: usize>(self) -> Self {
+        Simd::rotate_lanes_left::<K>(self)
+    }
+
+    #[inline(always)]
+    fn rotate_lanes_right<const K: usize>(self) -> Self {
+        Simd::rotate_lanes_right::<K>(self)
+    }
+
+    #[inline(always)]
+    fn broadcast<const I: usize>(self) -> Self {
+        Simd::broadcast::<I>(self)
+    }
+
+    forward! {
+        fn extract(idx: u32) -> T;
+        fn replace(idx: u32, elem: T) -> Self;
+        fn eq(rhs: Self) -> Self::Mask;
+        fn ne(rhs: Self) -> Self::Mask;
+        fn lt(rhs: Self) -> Self::Mask;
+        fn le(rhs: Self) -> Self::Mask;
+        fn gt(rhs: Self) -> Self::Mask;
+        fn ge(rhs: Self) -> Self::Mask;
+        fn max(rhs: Self) -> Self;
+        fn min(rhs: Self) -> Self;
+        fn sum() -> T;
+        fn product() -> T;
+        fn min_element() -> T;
+        fn max_element() -> T;
+        fn reverse() -> Self;
+        fn interleave(other: Self) -> (Self, Self);
+        fn deinterleave(other: Self) -> (Self, Self);
+        fn add_masked(rhs: Self, mask: Self::Mask) -> Self;
+        fn sub_masked(rhs: Self, mask: Self::Mask) -> Self;
+        fn mul_masked(rhs: Self, mask: Self::Mask) -> Self;
+        fn div_masked(rhs: Self, mask: Self::Mask) -> Self;
+    }
+}
+
+macro_rules! simd_float_impls {
+    ($($elem: ident),*) => {
+        $(impl<const N: usize> SimdFloat for Simd<$elem, N> where LaneCount<N>: SupportedLaneCount {
+            forward! {
+                fn sqrt() -> Self;
+                fn approx_rsqrt() -> Self;
+                fn approx_reciprocal() -> Self;
+                fn mul_add(a: Self, b: Self) -> Self;
+                fn mul_sub(a: Self, b: Self) -> Self;
+                fn neg_mul_add(a: Self, b: Self) -> Self;
+                fn neg_mul_sub(a: Self, b: Self) -> Self;
+                fn floor() -> Self;
+                fn ceil() -> Self;
+                fn round() -> Self;
+                fn round_ties_even() -> Self;
+                fn trunc() -> Self;
+                fn fract() -> Self;
+                fn ordered_sum() -> $elem;
+                fn exp() -> Self;
+                fn exp2() -> Self;
+                fn ln() -> Self;
+                fn log2() -> Self;
+                fn log10() -> Self;
+                fn sin_cos() -> (Self, Self);
+                fn sin() -> Self;
+                fn cos() -> Self;
+                fn tan() -> Self;
+                fn atan() -> Self;
+                fn atan2(x: Self) -> Self;
+                fn asin() -> Self;
+                fn acos() -> Self;
+                fn tanh() -> Self;
+                fn powf(n: Self) -> Self;
+                fn cbrt() -> Self;
+            }
+        })*
+    }
+}
+
+simd_float_impls! { f32, f64 }
+
+impl<T: IntElement, const N: usize> SimdInt for Simd<T, N> where LaneCount<N>: SupportedLaneCount {
+    forward! {
+        fn wrapping_add(rhs: Self) -> Self;
+        fn wrapping_sub(rhs: Self) -> Self;
+        fn wrapping_mul(rhs: Self) -> Self;
+        fn saturating_add(rhs: Self) -> Self;
+        fn saturating_sub(rhs: Self) -> Self;
+        fn wrapping_shl(n: u32) -> Self;
+        fn wrapping_shr(n: u32) -> Self;
+        fn rotate_left(n: u32) -> Self;
+        fn rotate_right(n: u32) -> Self;
+        fn rotate_left_by(n: Self) -> Self;
+        fn rotate_right_by(n: Self) -> Self;
+        fn and() -> T;
+        fn or() -> T;
+        fn xor() -> T;
+    }
+}
+
+impl<T: MaskElement, const N: usize> Sealed for Mask<T, N> where LaneCount<N>: SupportedLaneCount {}
+
+impl<T: MaskElement, const N: usize> SimdMask for Mask<T, N> where LaneCount<N>: SupportedLaneCount {
+    type Int = Simd<T, N>;
+    const LANES: usize = N;
+
+    #[inline(always)]
+    fn splat(x: bool) -> Self {
+        Mask::splat(x)
+    }
+
+    #[inline(always)]
+    fn load(array: &[bool], idx: usize) -> Self {
+        Mask::load(array, idx)
+    }
+
+    #[inline(always)]
+    fn store(self, array: &mut [bool], idx: usize) {
+        Mask::store(self, array, idx)
+    }
+
+    forward! {
+        fn extract(idx: u32) -> bool;
+        fn replace(idx: u32, elem: bool) -> Self;
+        fn all() -> bool;
+        fn any() -> bool;
+        fn to_i() -> Self::Int;
+    }
+}
@@ -0,0 +1,42 @@
+// In-register transposes of square matrices stored as one vector per row.
+//
+// Interleaving row `i` with row `i + N/2` for every `i`, and storing the halves
+// as rows `2i` and `2i + 1`, moves every element one bit of its index closer to
+// its transposed position. After log2(N) rounds the matrix is transposed, using
+// only the unpack shuffles of `interleave`.
+
+use {Simd, LaneCount, SupportedLaneCount, SimdElement, f64x4};
+
+#[inline(always)]
+fn transpose<T: SimdElement, const N: usize>(m: &mut [Simd<T, N>; N]) where LaneCount<N>: SupportedLaneCount {
+    let mut n = 1;
+    while n < N {
+        let rows = *m;
+        for i in 0..N / 2 {
+            let (lo, hi) = rows[i].interleave(rows[i + N / 2]);
+            m[2 * i] = lo;
+            m[2 * i + 1] = hi;
+        }
+        n *= 2;
+    }
+}
+
+/// Transpose a 4x4 matrix stored as 4 rows, such as `[f32x4; 4]`,
+/// `[f64x4; 4]` or `[i32x4; 4]`. Lane `j` of row `i` becomes lane `i` of row `j`.
+#[inline]
+pub fn transpose4<T: SimdElement>(m: &mut [Simd<T, 4>; 4]) {
+    transpose(m)
+}
+
+/// Transpose a 4x4 matrix of `f64`, the same as `transpose4` on `[f64x4; 4]`
+#[inline]
+pub fn transpose4_f64(m: &mut [f64x4; 4]) {
+    transpose4(m)
+}
+
+/// Transpose an 8x8 matrix stored as 8 rows, such as `[f32x8; 8]`,
+/// `[f64x8; 8]` or `[u16x8; 8]`. Lane `j` of row `i` becomes lane `i` of row `j`.
+#[inline]
+pub fn transpose8<T: SimdElement>(m: &mut [Simd<T, 8>; 8]) {
+    transpose(m)
+}
@@ -0,0 +1,52 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn from_and_into_arrays() {
+    let v = f32x4::from([1.0, 2.0, 3.0, 4.0]);
+    assert!(v.eq(f32x4::new(1.0, 2.0, 3.0, 4.0)).all());
+    let a: [f32; 4] = v.into();
+    assert_eq!(a, [1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(v.to_array(), a);
+
+    let w: u8x16 = [7; 16].into();
+    assert_eq!(w.sum(), 112);
+    assert_eq!(<[i64; 8]>::from(i64x8::splat(-2)), [-2; 8]);
+}
+
+#[test]
+fn array_views() {
+    let mut v = i32x8::new(0, 1, 2, 3, 4, 5, 6, 7);
+    assert_eq!(v.as_array()[5], 5);
+    assert_eq!(v.as_array().iter().sum::<i32>(), 28);
+
+    v.as_mut_array().reverse();
+    assert_eq!(v.extract(0), 7);
+    assert_eq!(v.extract(7), 0);
+
+    for x in v.as_mut_array().iter_mut() {
+        *x *= 10;
+    }
+    assert_eq!(v.to_array(), [70, 60, 50, 40, 30, 20, 10, 0]);
+}
+
+#[test]
+fn index_lanes() {
+    let mut v = f64x2::new(0.5, 1.5);
+    assert_eq!(v[0], 0.5);
+    assert_eq!(v[1], v.extract(1));
+    v[1] = -3.0;
+    v[0] += 1.0;
+    assert_eq!(v.to_array(), [1.5, -3.0]);
+
+    let mut w = u16x32::splat(1);
+    w[31] = 9;
+    assert_eq!(w.max_element(), 9);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_range() {
+    let v = u32x4::splat(1);
+    let _ = v[4];
+}
@@ -0,0 +1,107 @@
+extern crate ssimd;
+use ssimd::*;
+
+fn bytes() -> u8x16 {
+    u8x16::new(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 255)
+}
+
+#[test]
+fn new_and_lanes() {
+    let a = bytes();
+    for i in 0..15 {
+        assert_eq!(a.extract(i), i as u8);
+    }
+    assert_eq!(a.extract(15), 255);
+
+    let b = i8x16::new(-128, -1, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 127);
+    assert_eq!(b.extract(0), -128);
+    assert_eq!(b.extract(15), 127);
+    assert!(u8x16::splat(7).eq(u8x16::new(7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7)).all());
+}
+
+#[test]
+fn arithmetic_wraps() {
+    let a = u8x16::splat(255);
+    assert!((a + u8x16::splat(1)).eq(u8x16::splat(0)).all());
+    assert!((u8x16::splat(0) - u8x16::splat(1)).eq(u8x16::splat(255)).all());
+    assert!((u8x16::splat(16) * u8x16::splat(17)).eq(u8x16::splat(16)).all());
+
+    let b = i8x16::splat(i8::MIN);
+    assert!((b - i8x16::splat(1)).eq(i8x16::splat(i8::MAX)).all());
+    assert!((i8x16::splat(i8::MAX) + i8x16::splat(1)).eq(b).all());
+    assert!((i8x16::splat(-7) / i8x16::splat(2)).eq(i8x16::splat(-3)).all());
+}
+
+#[test]
+fn shifts_and_bitwise() {
+    let a = bytes();
+    assert_eq!((a << 1).extract(15), 254);
+    assert_eq!((a >> 4).extract(15), 15);
+    assert_eq!((i8x16::splat(-128) >> 7).extract(0), -1);
+    assert_eq!((a & u8x16::splat(0x0f)).extract(15), 0x0f);
+    assert_eq!((a | u8x16::splat(0xf0)).extract(1), 0xf1);
+    assert_eq!((a ^ u8x16::splat(0xff)).extract(15), 0);
+    assert_eq!((!a).extract(0), 255);
+}
+
+#[test]
+fn compare_to_mask() {
+    let a = bytes();
+    let m = a.gt(u8x16::splat(10));
+    for i in 0..16 {
+        assert_eq!(m.extract(i), i > 10);
+        assert_eq!(m.to_i().extract(i), if i > 10 { -1 } else { 0 });
+        assert_eq!(m.to_u().extract(i), if i > 10 { 255 } else { 0 });
+    }
+}
+
+#[test]
+fn signed_unsigned() {
+    let a = bytes();
+    assert_eq!(a.to_i().extract(15), -1);
+    assert_eq!(a.to_i().to_u().extract(15), 255);
+    assert_eq!(i8x16::splat(-128).to_u().extract(0), 128);
+}
+
+#[test]
+fn widen_halves() {
+    let a = bytes();
+    let (lo, hi) = (a.to_u16_lo(), a.to_u16_hi());
+    for i in 0..8 {
+        assert_eq!(lo.extract(i), i as u16);
+    }
+    assert_eq!(hi.extract(0), 8);
+    assert_eq!(hi.extract(7), 255);
+
+    let b = i8x16::new(-128, -1, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 127);
+    let (lo, hi) = (b.to_i16_lo(), b.to_i16_hi());
+    assert_eq!(lo.extract(0), -128);
+    assert_eq!(lo.extract(1), -1);
+    assert_eq!(lo.extract(7), 5);
+    assert_eq!(hi.extract(0), 6);
+    assert_eq!(hi.extract(7), 127);
+}
+
+#[test]
+fn widen_narrow_round_trip() {
+    let a = bytes();
+    assert!(u8x16::from_u16(a.to_u16_lo(), a.to_u16_hi()).eq(a).all());
+
+    let b = i8x16::new(-128, -1, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 127);
+    assert!(i8x16::from_i16(b.to_i16_lo(), b.to_i16_hi()).eq(b).all());
+}
+
+#[test]
+fn narrow_truncates() {
+    let lo = u16x8::new(0, 255, 256, 257, 0x1234, 0xffff, 1, 2);
+    let hi = u16x8::splat(0x0180);
+    let n = u8x16::from_u16(lo, hi);
+    let expected = [0, 255, 0, 1, 0x34, 0xff, 1, 2];
+    for i in 0..8 {
+        assert_eq!(n.extract(i), expected[i as usize]);
+        assert_eq!(n.extract(i + 8), 0x80);
+    }
+    let s = i8x16::from_i16(i16x8::splat(-129), i16x8::splat(128));
+    assert_eq!(s.extract(0), 127);
+    assert_eq!(s.extract(8), -128);
+}
@@ -0,0 +1,63 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn chunks_pad_the_tail() {
+    let data: Vec<f32> = (1..=10).map(|i| i as f32).collect();
+    let chunks: Vec<f32x4> = f32x4::chunks(&data).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].to_array(), [1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(chunks[2].to_array(), [9.0, 10.0, 0.0, 0.0]);
+
+    let last = f32x4::chunks_padded(&data, 1.0).last().unwrap();
+    assert_eq!(last.to_array(), [9.0, 10.0, 1.0, 1.0]);
+    assert_eq!(f32x4::chunks_padded(&data, 1.0).map(|v| v.product()).product::<f32>(), 3628800.0);
+
+    assert_eq!(u8x16::chunks(&[]).len(), 0);
+    assert_eq!(u8x16::chunks(&[0; 33]).len(), 3);
+    assert_eq!(u8x16::chunks(&[0; 32]).len(), 2);
+}
+
+#[test]
+fn chunks_mut_store_in_place() {
+    let mut data = [1i32, 2, 3, 4, 5, 6, 7];
+    for mut chunk in i32x4::chunks_mut(&mut data) {
+        let v = chunk.load();
+        chunk.store(v * 10 + 1);
+    }
+    assert_eq!(data, [11, 21, 31, 41, 51, 61, 71]);
+
+    let mut chunks = i32x4::chunks_mut(&mut data);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks.nth(1).unwrap().as_mut_slice(), &mut [51, 61, 71]);
+}
+
+#[test]
+fn zipped_chunks() {
+    let a = [1u16, 2, 3, 4, 5];
+    let b = [10u16, 20, 30, 40, 50];
+    let c = [100u16; 5];
+    let sum: u16 = u16x8::chunks_zip(&a, &b).map(|(x, y)| (x * y).sum()).sum();
+    assert_eq!(sum, 550);
+    let sum: u16 = Simd::<u16, 2>::chunks_zip3(&a, &b, &c).map(|(x, y, z)| (x + y + z).sum()).sum();
+    assert_eq!(sum, 665);
+}
+
+#[test]
+#[should_panic]
+fn zipped_chunks_length_mismatch() {
+    let _ = f64x2::chunks_zip(&[1.0, 2.0, 3.0], &[1.0, 2.0]);
+}
+
+#[test]
+fn map_whole_slices() {
+    for len in 0..40 {
+        let x: Vec<f64> = (0..len).map(|i| i as f64).collect();
+        let y: Vec<f64> = (0..len).map(|i| 2.0 * i as f64).collect();
+        let mut out = vec![-1.0; len];
+        f64x8::map_slice(&x, &mut out, |v| v * v);
+        assert!(out.iter().enumerate().all(|(i, &o)| o == (i * i) as f64));
+        f64x4::zip_map(&x, &y, &mut out, |a, b| b - a);
+        assert_eq!(out, x);
+    }
+}
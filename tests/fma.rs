@@ -0,0 +1,42 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn fma_single_rounding() {
+    // (1 + e) * (1 - e) = 1 - e^2 is rounded to 1 by a separate multiply
+    let e = f32::EPSILON;
+    let a = f32x4::splat(1.0 + e);
+    let b = f32x4::splat(1.0 - e);
+    let c = f32x4::splat(-1.0);
+    let one = f32x4::splat(1.0);
+    assert_eq!((a * b + c).extract(0), 0.0);
+    assert_eq!(a.mul_add(b, c).extract(0), -e * e);
+    assert_eq!(a.mul_sub(b, one).extract(1), -e * e);
+    assert_eq!(a.neg_mul_add(b, one).extract(2), e * e);
+    assert_eq!(a.neg_mul_sub(b, c).extract(3), e * e);
+
+    let e = f64::EPSILON;
+    let a = f64x4::splat(1.0 + e);
+    let b = f64x4::splat(1.0 - e);
+    let c = f64x4::splat(1.0);
+    assert_eq!(a.mul_sub(b, c).extract(0), -e * e);
+    assert_eq!(a.mul_sub(b, c).extract(0), (1.0 + e).mul_add(1.0 - e, -1.0));
+}
+
+#[test]
+fn fma_lanes() {
+    let a = f64x2::new(2.0, 3.0);
+    let b = f64x2::new(5.0, 7.0);
+    let c = f64x2::new(1.0, -1.0);
+    let r = a.mul_add(b, c);
+    assert_eq!((r.extract(0), r.extract(1)), (11.0, 20.0));
+    let r = a.mul_sub(b, c);
+    assert_eq!((r.extract(0), r.extract(1)), (9.0, 22.0));
+    let r = a.neg_mul_add(b, c);
+    assert_eq!((r.extract(0), r.extract(1)), (-9.0, -22.0));
+    let r = a.neg_mul_sub(b, c);
+    assert_eq!((r.extract(0), r.extract(1)), (-11.0, -20.0));
+
+    let r = f32x8::splat(2.0).mul_add(f32x8::splat(4.0), f32x8::splat(0.5));
+    assert_eq!(r.extract(7), 8.5);
+}
@@ -0,0 +1,76 @@
+extern crate ssimd;
+use ssimd::*;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn gather_with_each_index_type() {
+    let table: Vec<f32> = (0..100).map(|i| i as f32 * 0.5).collect();
+    let idx = u32x8::new(0, 99, 3, 3, 50, 7, 1, 20);
+    let v = f32x8::gather(&table, idx);
+    assert_eq!(v.to_array(), [0.0, 49.5, 1.5, 1.5, 25.0, 3.5, 0.5, 10.0]);
+    assert!(f32x8::gather(&table, idx.to_i()).eq(v).all());
+
+    let words = [10u64, 11, 12, 13];
+    let w = u64x4::gather(&words, u64x4::new(3, 2, 1, 0));
+    assert_eq!(w.to_array(), [13, 12, 11, 10]);
+    let bytes = [5u8, 6, 7];
+    let b = u8x16::gather(&bytes, Simd::<u64, 16>::splat(2));
+    assert_eq!(b.sum(), 112);
+
+    let unchecked = unsafe { f32x8::gather_unchecked(&table, idx) };
+    assert!(unchecked.eq(v).all());
+}
+
+#[test]
+fn gather_masked_skips_false_lanes() {
+    let table = [1i32, 2, 3];
+    // the index of a masked-off lane may be out of bounds, or negative
+    let idx = i32x4::new(2, -1, 0, 1000);
+    let mask = bool32x4::new(true, false, true, false);
+    let v = i32x4::gather_masked(&table, idx, mask, i32x4::splat(-7));
+    assert_eq!(v.to_array(), [3, -7, 1, -7]);
+    let u = unsafe { i32x4::gather_masked_unchecked(&table, idx, mask, i32x4::splat(-7)) };
+    assert!(u.eq(v).all());
+}
+
+#[test]
+#[should_panic]
+fn gather_out_of_bounds() {
+    f64x2::gather(&[1.0, 2.0], u32x2::new(0, 2));
+}
+
+#[test]
+#[should_panic]
+fn gather_negative_index() {
+    i32x4::gather(&[1, 2, 3, 4], i32x4::new(0, 1, -1, 2));
+}
+
+#[test]
+fn scatter_in_lane_order() {
+    let mut out = [0i16; 10];
+    i16x8::new(1, 2, 3, 4, 5, 6, 7, 8).scatter(&mut out, Simd::<u32, 8>::new(9, 0, 1, 1, 2, 3, 4, 5));
+    // lanes 2 and 3 share index 1: the last one wins
+    assert_eq!(out, [2, 4, 5, 6, 7, 8, 0, 0, 0, 1]);
+
+    let mut out = [0.0f64; 4];
+    f64x4::splat(1.0).scatter_masked(&mut out, i64x4::new(3, 99, 0, -5).to_u(),
+                                    bool64x4::new(true, false, true, false));
+    assert_eq!(out, [1.0, 0.0, 0.0, 1.0]);
+
+    let mut out = [0u32; 4];
+    unsafe {
+        u32x4::new(4, 3, 2, 1).scatter_unchecked(&mut out, u32x4::new(3, 2, 1, 0));
+        u32x4::splat(9).scatter_masked_unchecked(&mut out, u32x4::splat(0), bool32x4::new(false, false, false, true));
+    }
+    assert_eq!(out, [9, 2, 3, 4]);
+}
+
+#[test]
+fn scatter_out_of_bounds_stores_nothing() {
+    let mut out = [0u8; 4];
+    let r = panic::catch_unwind(AssertUnwindSafe(|| {
+        u8x16::splat(1).scatter(&mut out, u32x16::splat(0).replace(15, 4));
+    }));
+    assert!(r.is_err());
+    assert_eq!(out, [0; 4]);
+}
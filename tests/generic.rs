@@ -0,0 +1,46 @@
+extern crate ssimd;
+use ssimd::*;
+
+fn dot<const N: usize>(x: &[f32], y: &[f32]) -> f32 where LaneCount<N>: SupportedLaneCount {
+    let mut sum = Simd::<f32, N>::splat(0.0);
+    let mut i = 0;
+    while i + N <= x.len() {
+        sum += Simd::<f32, N>::load(x, i) * Simd::<f32, N>::load(y, i);
+        i += N;
+    }
+    sum.sum()
+}
+
+fn count_negative<T: SimdElement + From<i8>, const N: usize>(v: Simd<T, N>) -> usize
+    where LaneCount<N>: SupportedLaneCount
+{
+    let mask = v.lt(Simd::splat(T::from(0)));
+    (0..N as u32).filter(|&i| mask.extract(i)).count()
+}
+
+#[test]
+fn width_generic_kernels() {
+    let x: Vec<f32> = (0..16).map(|i| i as f32).collect();
+    let y = vec![2.0f32; 16];
+    assert_eq!(dot::<2>(&x, &y), 240.0);
+    assert_eq!(dot::<4>(&x, &y), 240.0);
+    assert_eq!(dot::<8>(&x, &y), 240.0);
+    assert_eq!(dot::<16>(&x, &y), 240.0);
+
+    assert_eq!(count_negative(i8x16::splat(-1).replace(3, 4)), 15);
+    assert_eq!(count_negative(f64x2::new(-0.5, 0.5)), 1);
+    assert_eq!(count_negative(Simd::<i32, 8>::splat(7)), 0);
+}
+
+#[test]
+fn aliases_are_generic_types() {
+    let a: Simd<f32, 4> = f32x4::new(1.0, 2.0, 3.0, 4.0);
+    let b: f32x4 = Simd::splat(1.0);
+    assert!((a + b).eq(f32x4::new(2.0, 3.0, 4.0, 5.0)).all());
+
+    let m: Mask<i32, 4> = a.gt(b);
+    let n: bool32x4 = m;
+    assert!(!n.extract(0) && n.extract(1));
+    assert_eq!(format!("{:?}", u16x8::splat(3)), "u16x8([3, 3, 3, 3, 3, 3, 3, 3])");
+    assert_eq!(format!("{:?}", bool64x2::new(true, false)), "bool64x2([-1, 0])");
+}
@@ -0,0 +1,104 @@
+extern crate ssimd;
+use ssimd::*;
+use std::mem::{align_of, size_of};
+
+macro_rules! layout_tests {
+    ($($test: ident, $name: ident, $size: expr;)*) => {
+        $(#[test]
+        fn $test() {
+            assert_eq!(size_of::<$name>(), $size);
+            assert_eq!(align_of::<$name>(), $size);
+        })*
+    }
+}
+
+layout_tests! {
+    layout_u32x2, u32x2, 8;
+    layout_i32x2, i32x2, 8;
+    layout_f32x2, f32x2, 8;
+    layout_bool32x2, bool32x2, 8;
+
+    layout_u32x4, u32x4, 16;
+    layout_i32x4, i32x4, 16;
+    layout_f32x4, f32x4, 16;
+    layout_bool32x4, bool32x4, 16;
+    layout_u16x8, u16x8, 16;
+    layout_i16x8, i16x8, 16;
+    layout_bool16x8, bool16x8, 16;
+    layout_u8x16, u8x16, 16;
+    layout_i8x16, i8x16, 16;
+    layout_bool8x16, bool8x16, 16;
+    layout_u64x2, u64x2, 16;
+    layout_i64x2, i64x2, 16;
+    layout_f64x2, f64x2, 16;
+    layout_bool64x2, bool64x2, 16;
+
+    layout_u64x4, u64x4, 32;
+    layout_i64x4, i64x4, 32;
+    layout_f64x4, f64x4, 32;
+    layout_bool64x4, bool64x4, 32;
+    layout_u32x8, u32x8, 32;
+    layout_i32x8, i32x8, 32;
+    layout_f32x8, f32x8, 32;
+    layout_bool32x8, bool32x8, 32;
+    layout_u16x16, u16x16, 32;
+    layout_i16x16, i16x16, 32;
+    layout_bool16x16, bool16x16, 32;
+    layout_u8x32, u8x32, 32;
+    layout_i8x32, i8x32, 32;
+    layout_bool8x32, bool8x32, 32;
+
+    layout_u32x16, u32x16, 64;
+    layout_i32x16, i32x16, 64;
+    layout_f32x16, f32x16, 64;
+    layout_bool32x16, bool32x16, 64;
+    layout_u64x8, u64x8, 64;
+    layout_i64x8, i64x8, 64;
+    layout_f64x8, f64x8, 64;
+    layout_bool64x8, bool64x8, 64;
+    layout_u16x32, u16x32, 64;
+    layout_i16x32, i16x32, 64;
+    layout_bool16x32, bool16x32, 64;
+    layout_u8x64, u8x64, 64;
+    layout_i8x64, i8x64, 64;
+    layout_bool8x64, bool8x64, 64;
+}
+
+#[test]
+fn lane_access() {
+    let v = f32x4::new(1.0, 2.0, 3.0, 4.0).replace(2, 5.0);
+    assert_eq!(v.extract(0), 1.0);
+    assert_eq!(v.extract(2), 5.0);
+    assert_eq!(v.extract(3), 4.0);
+
+    let mut out = [0u8; 40];
+    u8x32::splat(7).replace(31, 9).store(&mut out, 3);
+    assert_eq!(u8x32::load(&out, 3).extract(31), 9);
+    assert_eq!(out[2], 0);
+    assert_eq!(out[34], 9);
+}
+
+#[test]
+#[should_panic]
+fn extract_out_of_range() {
+    f64x2::splat(1.0).extract(2);
+}
+
+#[test]
+fn wide_vectors() {
+    let a = f32x16::new(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0,
+                        8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0);
+    assert_eq!((a * 2.0).sum(), 240.0);
+    assert_eq!(a.to_i().extract(15), 15);
+    assert_eq!(a.sqrt().extract(9), 3.0);
+
+    let b = f64x8::splat(1.0).exp().ln();
+    assert_eq!(b.extract(7), 1.0);
+    assert_eq!(b.to_f32().extract(3), 1.0);
+
+    let c = u8x64::splat(250).saturating_add(u8x64::splat(10));
+    assert_eq!(c.max_element(), 255);
+    assert!(i16x32::splat(-3).lt(i16x32::splat(0)).all());
+    assert_eq!((-i8x64::splat(5)).to_u().extract(63), 251);
+    assert_eq!(i64x8::splat(-1).to_i32().extract(7), -1);
+}
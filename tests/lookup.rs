@@ -0,0 +1,57 @@
+extern crate ssimd;
+use ssimd::*;
+
+// Run `f` on every index byte, 16 or 32 at a time
+fn all_indices<const N: usize>(mut f: impl FnMut([u8; N])) where LaneCount<N>: SupportedLaneCount {
+    for start in (0..256).step_by(N) {
+        f(std::array::from_fn(|i| (start + i) as u8));
+    }
+}
+
+#[test]
+fn lookup_u8x16() {
+    let table: [u8; 16] = std::array::from_fn(|i| (i * 7 + 3) as u8);
+    let t = u8x16::from(table);
+    all_indices(|idx: [u8; 16]| {
+        let r = t.lookup(u8x16::from(idx));
+        let z = t.lookup_or_zero(u8x16::from(idx));
+        for i in 0..16 {
+            let j = idx[i];
+            assert_eq!(r[i], if j & 0x80 != 0 { 0 } else { table[(j & 15) as usize] }, "lookup {}", j);
+            assert_eq!(z[i], if j < 16 { table[j as usize] } else { 0 }, "lookup_or_zero {}", j);
+        }
+    });
+}
+
+#[test]
+fn lookup_u8x32() {
+    let table: [u8; 32] = std::array::from_fn(|i| (i * 5 + 1) as u8);
+    let t = u8x32::from(table);
+    all_indices(|idx: [u8; 32]| {
+        // Reverse the indices so the two halves see different ones
+        let mut idx = idx;
+        idx[16..].reverse();
+        let r = t.lookup(u8x32::from(idx));
+        let w = t.lookup_wide(u8x32::from(idx));
+        for i in 0..32 {
+            let j = idx[i];
+            let half = i / 16 * 16;
+            assert_eq!(r[i], if j & 0x80 != 0 { 0 } else { table[half + (j & 15) as usize] }, "lookup {}", j);
+            assert_eq!(w[i], if j < 32 { table[j as usize] } else { 0 }, "lookup_wide {}", j);
+        }
+    });
+}
+
+#[test]
+fn hex_encode() {
+    let digits = u8x16::from(*b"0123456789abcdef");
+    let bytes = u8x16::from([0x00, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd,
+                             0xef, 0xff, 0x10, 0x32, 0x54, 0x76, 0x98, 0xba]);
+    let hi = digits.lookup(bytes >> 4);
+    let lo = digits.lookup(bytes & u8x16::splat(0x0f));
+    let (a, b) = hi.interleave(lo);
+    let mut out = [0; 32];
+    a.store(&mut out, 0);
+    b.store(&mut out, 16);
+    assert_eq!(&out[..], &b"000123456789abcdefff1032547698ba"[..]);
+}
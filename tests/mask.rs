@@ -0,0 +1,87 @@
+extern crate ssimd;
+use ssimd::*;
+
+macro_rules! mask_tests {
+    ($(
+        $test: ident, $name: ident : $elem: ident, $bool_name: ident,
+        $uname: ident : $uelem: ident, $iname: ident : $ielem: ident, $length: expr;
+    )*) => {
+        $(#[test]
+        fn $test() {
+            let mut a = $name::splat(0 as $elem);
+            let mut b = $name::splat(0 as $elem);
+            for i in 0..$length {
+                a = a.replace(i, i as $elem);
+                b = b.replace(i, ($length - 1 - i) as $elem);
+            }
+
+            let mask = a.lt(b);
+            for i in 0..$length {
+                let lane = i < $length - 1 - i;
+                assert_eq!(mask.extract(i), lane);
+                assert_eq!((!mask).extract(i), !lane);
+                assert_eq!(mask.to_i().extract(i), if lane { -1 } else { 0 });
+                assert_eq!(mask.to_u().extract(i), if lane { !0 } else { 0 });
+            }
+
+            // operands with arbitrary bit patterns, not only 0 and 1
+            let then = $uname::splat(!0 / 3).replace(0, 0xa5);
+            let else_ = $uname::splat(!0 / 5).replace(1, !0);
+            let selected = mask.to_u().select(then, else_);
+            for i in 0..$length {
+                let x = if mask.extract(i) { then.extract(i) } else { else_.extract(i) };
+                assert_eq!(selected.extract(i), x);
+            }
+
+            let then = $iname::splat(-7).replace(0, <$ielem>::MIN);
+            let else_ = $iname::splat(<$ielem>::MAX).replace(1, 42);
+            let selected = mask.to_i().select(then, else_);
+            for i in 0..$length {
+                let x = if mask.extract(i) { then.extract(i) } else { else_.extract(i) };
+                assert_eq!(selected.extract(i), x);
+            }
+
+            assert!($bool_name::splat(true).all());
+            assert!(!$bool_name::splat(false).any());
+            assert!($bool_name::splat(false).replace(1, true).any());
+            assert!(!$bool_name::splat(true).replace(1, false).all());
+            assert_eq!($bool_name::splat(true).to_u().extract(0), <$uelem>::MAX);
+        })*
+    }
+}
+
+mask_tests! {
+    mask_u32x2, u32x2:u32, bool32x2, u32x2:u32, i32x2:i32, 2;
+    mask_i32x2, i32x2:i32, bool32x2, u32x2:u32, i32x2:i32, 2;
+    mask_f32x2, f32x2:f32, bool32x2, u32x2:u32, i32x2:i32, 2;
+    mask_u32x4, u32x4:u32, bool32x4, u32x4:u32, i32x4:i32, 4;
+    mask_i32x4, i32x4:i32, bool32x4, u32x4:u32, i32x4:i32, 4;
+    mask_f32x4, f32x4:f32, bool32x4, u32x4:u32, i32x4:i32, 4;
+    mask_u16x8, u16x8:u16, bool16x8, u16x8:u16, i16x8:i16, 8;
+    mask_i16x8, i16x8:i16, bool16x8, u16x8:u16, i16x8:i16, 8;
+    mask_u8x16, u8x16:u8, bool8x16, u8x16:u8, i8x16:i8, 16;
+    mask_i8x16, i8x16:i8, bool8x16, u8x16:u8, i8x16:i8, 16;
+    mask_u64x2, u64x2:u64, bool64x2, u64x2:u64, i64x2:i64, 2;
+    mask_i64x2, i64x2:i64, bool64x2, u64x2:u64, i64x2:i64, 2;
+    mask_f64x2, f64x2:f64, bool64x2, u64x2:u64, i64x2:i64, 2;
+    mask_u64x4, u64x4:u64, bool64x4, u64x4:u64, i64x4:i64, 4;
+    mask_i64x4, i64x4:i64, bool64x4, u64x4:u64, i64x4:i64, 4;
+    mask_f64x4, f64x4:f64, bool64x4, u64x4:u64, i64x4:i64, 4;
+    mask_u32x8, u32x8:u32, bool32x8, u32x8:u32, i32x8:i32, 8;
+    mask_i32x8, i32x8:i32, bool32x8, u32x8:u32, i32x8:i32, 8;
+    mask_f32x8, f32x8:f32, bool32x8, u32x8:u32, i32x8:i32, 8;
+    mask_u16x16, u16x16:u16, bool16x16, u16x16:u16, i16x16:i16, 16;
+    mask_i16x16, i16x16:i16, bool16x16, u16x16:u16, i16x16:i16, 16;
+    mask_u8x32, u8x32:u8, bool8x32, u8x32:u8, i8x32:i8, 32;
+    mask_i8x32, i8x32:i8, bool8x32, u8x32:u8, i8x32:i8, 32;
+    mask_u32x16, u32x16:u32, bool32x16, u32x16:u32, i32x16:i32, 16;
+    mask_i32x16, i32x16:i32, bool32x16, u32x16:u32, i32x16:i32, 16;
+    mask_f32x16, f32x16:f32, bool32x16, u32x16:u32, i32x16:i32, 16;
+    mask_u64x8, u64x8:u64, bool64x8, u64x8:u64, i64x8:i64, 8;
+    mask_i64x8, i64x8:i64, bool64x8, u64x8:u64, i64x8:i64, 8;
+    mask_f64x8, f64x8:f64, bool64x8, u64x8:u64, i64x8:i64, 8;
+    mask_u16x32, u16x32:u16, bool16x32, u16x32:u16, i16x32:i16, 32;
+    mask_i16x32, i16x32:i16, bool16x32, u16x32:u16, i16x32:i16, 32;
+    mask_u8x64, u8x64:u8, bool8x64, u8x64:u8, i8x64:i8, 64;
+    mask_i8x64, i8x64:i8, bool8x64, u8x64:u8, i8x64:i8, 64;
+}
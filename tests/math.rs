@@ -0,0 +1,251 @@
+extern crate ssimd;
+use ssimd::*;
+
+fn ulp32(a: f32, b: f32) -> u64 {
+    if (a.is_nan() && b.is_nan()) || a == b {
+        return 0;
+    }
+    if a.is_nan() || b.is_nan() || a.is_sign_negative() != b.is_sign_negative() {
+        return u64::MAX;
+    }
+    (i64::from(a.to_bits()) - i64::from(b.to_bits())).unsigned_abs()
+}
+
+fn ulp64(a: f64, b: f64) -> u64 {
+    if (a.is_nan() && b.is_nan()) || a == b {
+        return 0;
+    }
+    if a.is_nan() || b.is_nan() || a.is_sign_negative() != b.is_sign_negative() {
+        return u64::MAX;
+    }
+    (i128::from(a.to_bits()) - i128::from(b.to_bits())).unsigned_abs() as u64
+}
+
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn uniform(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * ((self.next() >> 11) as f64 / (1u64 << 53) as f64)
+    }
+
+    // any finite or infinite positive value, uniform over the bit patterns
+    fn bits32(&mut self) -> f32 {
+        f32::from_bits(self.next() as u32 & 0x7f80_0000)
+            .max(f32::from_bits(self.next() as u32 & 0x7fff_ffff))
+    }
+
+    fn bits64(&mut self) -> f64 {
+        f64::from_bits(self.next() & 0x7fff_ffff_ffff_ffff)
+    }
+}
+
+// Miri is orders of magnitude slower, so it checks fewer random inputs
+const SAMPLES: usize = if cfg!(miri) { 200 } else { 20_000 };
+
+macro_rules! check32 {
+    ($max_ulp: expr, $range: expr, $vector: expr, $scalar: expr) => {{
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..SAMPLES {
+            let x = [$range(&mut rng) as f32, $range(&mut rng) as f32,
+                     $range(&mut rng) as f32, $range(&mut rng) as f32];
+            let v = $vector(f32x4::load(&x, 0));
+            for i in 0..4 {
+                let expected = $scalar(x[i]);
+                let ulp = ulp32(v.extract(i as u32), expected);
+                assert!(ulp <= $max_ulp, "{:e}: got {:e}, expected {:e} ({} ulp)",
+                        x[i], v.extract(i as u32), expected, ulp);
+            }
+        }
+    }}
+}
+
+macro_rules! check64 {
+    ($max_ulp: expr, $range: expr, $vector: expr, $scalar: expr) => {{
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..SAMPLES {
+            let x = [$range(&mut rng), $range(&mut rng)];
+            let v = $vector(f64x2::load(&x, 0));
+            for i in 0..2 {
+                let expected = $scalar(x[i]);
+                let ulp = ulp64(v.extract(i as u32), expected);
+                assert!(ulp <= $max_ulp, "{:e}: got {:e}, expected {:e} ({} ulp)",
+                        x[i], v.extract(i as u32), expected, ulp);
+            }
+        }
+    }}
+}
+
+const SPECIAL32: [f32; 14] = [0.0, -0.0, 1.0, -1.0, 0.5, -0.5, 2.0, -3.0, 1.0e-40, -1.0e-40,
+                              f32::MAX, f32::INFINITY, f32::NEG_INFINITY, f32::NAN];
+const SPECIAL64: [f64; 14] = [0.0, -0.0, 1.0, -1.0, 0.5, -0.5, 2.0, -3.0, 1.0e-310, -1.0e-310,
+                              f64::MAX, f64::INFINITY, f64::NEG_INFINITY, f64::NAN];
+
+macro_rules! special32 {
+    ($max_ulp: expr, $vector: expr, $scalar: expr) => {{
+        for &x in SPECIAL32.iter() {
+            let got = $vector(f32x4::splat(x)).extract(0);
+            let expected = $scalar(x);
+            assert!(ulp32(got, expected) <= $max_ulp, "{:e}: got {:e}, expected {:e}", x, got, expected);
+        }
+    }}
+}
+
+macro_rules! special64 {
+    ($max_ulp: expr, $vector: expr, $scalar: expr) => {{
+        for &x in SPECIAL64.iter() {
+            let got = $vector(f64x2::splat(x)).extract(0);
+            let expected = $scalar(x);
+            assert!(ulp64(got, expected) <= $max_ulp, "{:e}: got {:e}, expected {:e}", x, got, expected);
+        }
+    }}
+}
+
+#[test]
+fn exp() {
+    check32!(1, |r: &mut Rng| r.uniform(-104.0, 89.0), |v: f32x4| v.exp(), |x: f32| x.exp());
+    check64!(2, |r: &mut Rng| r.uniform(-746.0, 710.0), |v: f64x2| v.exp(), |x: f64| x.exp());
+    check32!(1, |r: &mut Rng| r.uniform(-151.0, 129.0), |v: f32x4| v.exp2(), |x: f32| x.exp2());
+    check64!(2, |r: &mut Rng| r.uniform(-1076.0, 1025.0), |v: f64x2| v.exp2(), |x: f64| x.exp2());
+    special32!(1, |v: f32x4| v.exp(), |x: f32| x.exp());
+    special64!(2, |v: f64x2| v.exp(), |x: f64| x.exp());
+    special32!(1, |v: f32x4| v.exp2(), |x: f32| x.exp2());
+    special64!(2, |v: f64x2| v.exp2(), |x: f64| x.exp2());
+}
+
+#[test]
+fn logarithms() {
+    check32!(1, Rng::bits32, |v: f32x4| v.ln(), |x: f32| x.ln());
+    check64!(1, Rng::bits64, |v: f64x2| v.ln(), |x: f64| x.ln());
+    check32!(1, Rng::bits32, |v: f32x4| v.log2(), |x: f32| x.log2());
+    check64!(1, Rng::bits64, |v: f64x2| v.log2(), |x: f64| x.log2());
+    check32!(2, Rng::bits32, |v: f32x4| v.log10(), |x: f32| x.log10());
+    check64!(2, Rng::bits64, |v: f64x2| v.log10(), |x: f64| x.log10());
+    check32!(1, |r: &mut Rng| r.uniform(0.5, 2.0), |v: f32x4| v.ln(), |x: f32| x.ln());
+    check64!(1, |r: &mut Rng| r.uniform(0.5, 2.0), |v: f64x2| v.ln(), |x: f64| x.ln());
+    special32!(0, |v: f32x4| v.ln(), |x: f32| x.ln());
+    special64!(0, |v: f64x2| v.ln(), |x: f64| x.ln());
+    special32!(0, |v: f32x4| v.log2(), |x: f32| x.log2());
+    special64!(0, |v: f64x2| v.log2(), |x: f64| x.log2());
+    special32!(1, |v: f32x4| v.log10(), |x: f32| x.log10());
+    special64!(1, |v: f64x2| v.log10(), |x: f64| x.log10());
+}
+
+#[test]
+fn trigonometry() {
+    for &limit in [10.0, 8192.0].iter() {
+        check32!(2, |r: &mut Rng| r.uniform(-limit, limit), |v: f32x4| v.sin(), |x: f32| x.sin());
+        check32!(2, |r: &mut Rng| r.uniform(-limit, limit), |v: f32x4| v.cos(), |x: f32| x.cos());
+        check32!(4, |r: &mut Rng| r.uniform(-limit, limit), |v: f32x4| v.tan(), |x: f32| x.tan());
+    }
+    for &limit in [10.0, 1.0e6].iter() {
+        check64!(2, |r: &mut Rng| r.uniform(-limit, limit), |v: f64x2| v.sin(), |x: f64| x.sin());
+        check64!(2, |r: &mut Rng| r.uniform(-limit, limit), |v: f64x2| v.cos(), |x: f64| x.cos());
+        check64!(4, |r: &mut Rng| r.uniform(-limit, limit), |v: f64x2| v.tan(), |x: f64| x.tan());
+    }
+    special32!(2, |v: f32x4| v.sin(), |x: f32| x.sin());
+    special64!(2, |v: f64x2| v.cos(), |x: f64| x.cos());
+    special32!(4, |v: f32x4| v.tan(), |x: f32| x.tan());
+
+    // beyond the reduction range, the std functions are used
+    let x = f32x4::new(1.0, 2.0, 1.0e7, 3.0);
+    assert_eq!(x.sin().extract(2), 1.0e7f32.sin());
+    assert_eq!(x.cos().extract(2), 1.0e7f32.cos());
+
+    let (s, c) = f64x4::new(0.1, 1.0, -2.5, 300.0).sin_cos();
+    for (i, &x) in [0.1f64, 1.0, -2.5, 300.0].iter().enumerate() {
+        assert!(ulp64(s.extract(i as u32), x.sin()) <= 2);
+        assert!(ulp64(c.extract(i as u32), x.cos()) <= 2);
+    }
+}
+
+#[test]
+fn inverse_trigonometry() {
+    let signed32 = |r: &mut Rng| if r.next() & 1 == 0 { r.bits32() } else { -r.bits32() };
+    let signed64 = |r: &mut Rng| if r.next() & 1 == 0 { r.bits64() } else { -r.bits64() };
+    check32!(3, signed32, |v: f32x4| v.atan(), |x: f32| x.atan());
+    check64!(1, signed64, |v: f64x2| v.atan(), |x: f64| x.atan());
+    check32!(3, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f32x4| v.atan(), |x: f32| x.atan());
+    check64!(1, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f64x2| v.atan(), |x: f64| x.atan());
+    check32!(1, |r: &mut Rng| r.uniform(-1.0, 1.0), |v: f32x4| v.asin(), |x: f32| x.asin());
+    check64!(1, |r: &mut Rng| r.uniform(-1.0, 1.0), |v: f64x2| v.asin(), |x: f64| x.asin());
+    check32!(1, |r: &mut Rng| r.uniform(-1.0, 1.0), |v: f32x4| v.acos(), |x: f32| x.acos());
+    check64!(1, |r: &mut Rng| r.uniform(-1.0, 1.0), |v: f64x2| v.acos(), |x: f64| x.acos());
+    special32!(3, |v: f32x4| v.atan(), |x: f32| x.atan());
+    special64!(1, |v: f64x2| v.atan(), |x: f64| x.atan());
+    special32!(1, |v: f32x4| v.asin(), |x: f32| x.asin());
+    special64!(1, |v: f64x2| v.asin(), |x: f64| x.asin());
+    special32!(1, |v: f32x4| v.acos(), |x: f32| x.acos());
+    special64!(1, |v: f64x2| v.acos(), |x: f64| x.acos());
+
+    // A NaN `y` gives NaN for any `x`. This is spelled out because Miri's
+    // `atan2` returns pi/2 for a NaN `y` and a zero `x`.
+    let atan2_32 = |y: f32, x: f32| if y.is_nan() { f32::NAN } else { y.atan2(x) };
+    let atan2_64 = |y: f64, x: f64| if y.is_nan() { f64::NAN } else { y.atan2(x) };
+    for &y in SPECIAL32.iter() {
+        check32!(3, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f32x4| f32x4::splat(y).atan2(v), |x: f32| atan2_32(y, x));
+        check32!(3, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f32x4| v.atan2(f32x4::splat(y)), |x: f32| x.atan2(y));
+        special32!(3, |v: f32x4| f32x4::splat(y).atan2(v), |x: f32| atan2_32(y, x));
+    }
+    for &y in SPECIAL64.iter() {
+        check64!(2, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f64x2| f64x2::splat(y).atan2(v), |x: f64| atan2_64(y, x));
+        check64!(2, |r: &mut Rng| r.uniform(-5.0, 5.0), |v: f64x2| v.atan2(f64x2::splat(y)), |x: f64| x.atan2(y));
+        special64!(2, |v: f64x2| f64x2::splat(y).atan2(v), |x: f64| atan2_64(y, x));
+    }
+}
+
+#[test]
+fn tanh() {
+    check32!(2, |r: &mut Rng| r.uniform(-10.0, 10.0), |v: f32x4| v.tanh(), |x: f32| x.tanh());
+    check64!(2, |r: &mut Rng| r.uniform(-20.0, 20.0), |v: f64x2| v.tanh(), |x: f64| x.tanh());
+    special32!(2, |v: f32x4| v.tanh(), |x: f32| x.tanh());
+    special64!(2, |v: f64x2| v.tanh(), |x: f64| x.tanh());
+}
+
+#[test]
+fn powf() {
+    check32!(1, |r: &mut Rng| r.uniform(0.0, 30.0), |v: f32x4| v.powf(f32x4::splat(3.7)), |x: f32| x.powf(3.7));
+    check32!(1, Rng::bits32, |v: f32x4| v.powf(f32x4::splat(-2.37)), |x: f32| x.powf(-2.37));
+    check32!(1, |r: &mut Rng| r.uniform(-30.0, 30.0), |v: f32x4| f32x4::splat(1.7).powf(v), |y: f32| 1.7f32.powf(y));
+    check64!(1, |r: &mut Rng| r.uniform(0.0, 30.0), |v: f64x2| v.powf(f64x2::splat(3.7)), |x: f64| x.powf(3.7));
+    check64!(1, Rng::bits64, |v: f64x2| v.powf(f64x2::splat(0.37)), |x: f64| x.powf(0.37));
+    check64!(1, |r: &mut Rng| r.uniform(-700.0, 700.0), |v: f64x2| f64x2::splat(2.7).powf(v), |y: f64| 2.7f64.powf(y));
+    check64!(1, |r: &mut Rng| r.uniform(0.9, 1.1), |v: f64x2| v.powf(f64x2::splat(-1234.5)), |x: f64| x.powf(-1234.5));
+
+    for &x in SPECIAL32.iter() {
+        special32!(1, |v: f32x4| f32x4::splat(x).powf(v), |y: f32| x.powf(y));
+    }
+    for &x in SPECIAL64.iter() {
+        special64!(1, |v: f64x2| f64x2::splat(x).powf(v), |y: f64| x.powf(y));
+    }
+}
+
+#[test]
+fn cbrt() {
+    let signed32 = |r: &mut Rng| if r.next() & 1 == 0 { r.bits32() } else { -r.bits32() };
+    let signed64 = |r: &mut Rng| if r.next() & 1 == 0 { r.bits64() } else { -r.bits64() };
+    check32!(1, signed32, |v: f32x4| v.cbrt(), |x: f32| x.cbrt());
+    check64!(1, signed64, |v: f64x2| v.cbrt(), |x: f64| x.cbrt());
+    special32!(0, |v: f32x4| v.cbrt(), |x: f32| x.cbrt());
+    special64!(0, |v: f64x2| v.cbrt(), |x: f64| x.cbrt());
+}
+
+#[test]
+fn all_widths() {
+    let x = f32x8::new(0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8);
+    let y = x.exp();
+    let z = f32x2::new(0.25, 0.75).asin();
+    let w = f64x4::new(1.0, 8.0, 27.0, -64.0).cbrt();
+    for i in 0..8 {
+        assert!(ulp32(y.extract(i), x.extract(i).exp()) <= 1);
+    }
+    assert_eq!(z.extract(1), 0.75f32.asin());
+    assert_eq!(w.extract(3), -4.0);
+    assert_eq!(f64x4::splat(2.0).powf(f64x4::new(0.5, 1.0, 2.0, 10.0)).extract(3), 1024.0);
+}
@@ -0,0 +1,77 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn arithmetic_assign() {
+    let mut a = f32x4::new(1.0, 2.0, 3.0, 4.0);
+    a += f32x4::splat(1.0);
+    a *= f32x4::splat(2.0);
+    a -= f32x4::splat(0.5);
+    a /= f32x4::splat(0.5);
+    assert!(a.eq(f32x4::new(7.0, 11.0, 15.0, 19.0)).all());
+
+    let mut b = u8x32::splat(200);
+    b += u8x32::splat(100);
+    assert!(b.eq(u8x32::splat(44)).all());
+    b -= u8x32::splat(45);
+    assert!(b.eq(u8x32::splat(255)).all());
+    b /= u8x32::splat(5);
+    b *= u8x32::splat(3);
+    assert!(b.eq(u8x32::splat(153)).all());
+}
+
+#[test]
+fn bitwise_assign() {
+    let mut a = u32x8::splat(0b1100);
+    a &= u32x8::splat(0b1010);
+    assert!(a.eq(u32x8::splat(0b1000)).all());
+    a |= u32x8::splat(0b0001);
+    a ^= u32x8::splat(0b1111);
+    assert!(a.eq(u32x8::splat(0b0110)).all());
+    a <<= 2;
+    assert!(a.eq(u32x8::splat(0b11000)).all());
+    a >>= 3;
+    assert!(a.eq(u32x8::splat(0b11)).all());
+
+    let mut m = bool16x8::new(true, true, false, false, true, false, true, false);
+    m &= bool16x8::splat(true);
+    m |= bool16x8::new(false, false, true, false, false, false, false, false);
+    m ^= bool16x8::splat(true);
+    for (i, &expected) in [false, false, false, true, false, true, false, true].iter().enumerate() {
+        assert_eq!(m.extract(i as u32), expected);
+    }
+}
+
+#[test]
+fn neg() {
+    let a = -f64x4::new(1.0, -2.0, 0.0, f64::INFINITY);
+    assert_eq!(a.extract(0), -1.0);
+    assert_eq!(a.extract(1), 2.0);
+    assert!(a.extract(2).is_sign_negative());
+    assert_eq!(a.extract(3), f64::NEG_INFINITY);
+
+    let b = -i8x16::new(1, -1, 0, i8::MAX, i8::MIN, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    assert_eq!(b.extract(0), -1);
+    assert_eq!(b.extract(1), 1);
+    assert_eq!(b.extract(3), -i8::MAX);
+    assert_eq!(b.extract(4), i8::MIN);
+    assert!((-i32x4::splat(7)).eq(i32x4::splat(-7)).all());
+    assert!((-f32x8::splat(2.5)).eq(f32x8::splat(-2.5)).all());
+}
+
+#[test]
+fn rem() {
+    let a = i32x4::new(7, -7, 7, -7);
+    let b = i32x4::new(3, 3, -3, -3);
+    assert!((a % b).eq(i32x4::new(1, -1, 1, -1)).all());
+
+    let mut c = u16x16::splat(1000);
+    c %= u16x16::splat(7);
+    assert!(c.eq(u16x16::splat(6)).all());
+}
+
+#[test]
+#[should_panic]
+fn rem_by_zero() {
+    let _ = u64x2::splat(1) % u64x2::new(1, 0);
+}
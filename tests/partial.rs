@@ -0,0 +1,85 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn partial_load_and_store() {
+    let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let v = f32x4::load_partial(&data, 4, -1.0);
+    assert_eq!(v.to_array(), [5.0, 6.0, -1.0, -1.0]);
+    assert_eq!(f32x4::load_partial(&data, 1, 0.0).to_array(), [2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(f32x4::load_partial(&data, 6, 9.0).to_array(), [9.0; 4]);
+
+    let mut out = [0u16; 5];
+    u16x8::splat(7).store_partial(&mut out, 2);
+    assert_eq!(out, [0, 0, 7, 7, 7]);
+    u16x8::splat(1).store_partial(&mut out, 5);
+    assert_eq!(out, [0, 0, 7, 7, 7]);
+}
+
+#[test]
+#[should_panic]
+fn partial_load_past_end() {
+    f64x2::load_partial(&[1.0, 2.0], 3, 0.0);
+}
+
+// Same message as `load_partial`, not an arithmetic overflow in debug builds
+#[test]
+#[should_panic(expected = "range start index 3 out of range for slice of length 2")]
+fn partial_store_past_end() {
+    f64x2::splat(1.0).store_partial(&mut [0.0; 2], 3);
+}
+
+#[test]
+fn masked_load_and_store() {
+    let data = [10i32, 20, 30, 40, 50];
+    let mask = bool32x4::new(true, false, true, true);
+    let v = i32x4::load_masked(&data, 0, mask, i32x4::splat(-1));
+    assert_eq!(v.to_array(), [10, -1, 30, 40]);
+
+    // true lanes past the end of the slice are not read
+    let v = i32x4::load_masked(&data, 3, bool32x4::splat(true), i32x4::splat(-1));
+    assert_eq!(v.to_array(), [40, 50, -1, -1]);
+    let v = i32x4::load_masked(&data, 5, bool32x4::splat(true), i32x4::splat(0));
+    assert_eq!(v.to_array(), [0; 4]);
+
+    let mut out = [0i32; 6];
+    i32x4::new(1, 2, 3, 4).store_masked(&mut out, 0, mask);
+    assert_eq!(out, [1, 0, 3, 4, 0, 0]);
+    i32x4::splat(9).store_masked(&mut out, 4, bool32x4::splat(true));
+    assert_eq!(out, [1, 0, 3, 4, 9, 9]);
+    i32x4::splat(5).store_masked(&mut out, 6, bool32x4::splat(true));
+    assert_eq!(out, [1, 0, 3, 4, 9, 9]);
+}
+
+// Like the partial forms, an `idx` past the end is a bug, not an empty tail
+#[test]
+#[should_panic(expected = "range start index 6 out of range for slice of length 5")]
+fn masked_load_past_end() {
+    i32x4::load_masked(&[1, 2, 3, 4, 5], 6, bool32x4::splat(false), i32x4::splat(0));
+}
+
+#[test]
+#[should_panic(expected = "range start index 100 out of range for slice of length 6")]
+fn masked_store_past_end() {
+    i32x4::splat(5).store_masked(&mut [0; 6], 100, bool32x4::splat(false));
+}
+
+fn sum_all<V: SimdVector<Elem = u8>>(data: &[u8]) -> u8 {
+    let mut sum = V::splat(0);
+    let mut i = 0;
+    while i < data.len() {
+        sum += V::load_partial(data, i, 0);
+        i += V::LANES;
+    }
+    sum.sum()
+}
+
+#[test]
+fn arbitrary_lengths() {
+    for len in 0..70 {
+        let data: Vec<u8> = (0..len as u8).collect();
+        let expected = data.iter().fold(0u8, |a, &b| a.wrapping_add(b));
+        assert_eq!(sum_all::<u8x16>(&data), expected);
+        assert_eq!(sum_all::<u8x64>(&data), expected);
+    }
+}
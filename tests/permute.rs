@@ -0,0 +1,88 @@
+extern crate ssimd;
+use ssimd::*;
+
+// Each operation is checked lane by lane against the same permutation of the
+// lanes as an array
+macro_rules! permute_tests {
+    ($($test: ident, $name: ident, $elem: ident, $lanes: expr;)*) => {
+        $(#[test]
+        fn $test() {
+            let a: [$elem; $lanes] = std::array::from_fn(|i| (i + 1) as $elem);
+            let b: [$elem; $lanes] = std::array::from_fn(|i| (i + 101) as $elem);
+            let (va, vb) = ($name::from(a), $name::from(b));
+            let n = $lanes;
+
+            let r = va.reverse();
+            for i in 0..n {
+                assert_eq!(r[i], a[n - 1 - i]);
+            }
+
+            let l = va.rotate_lanes_left::<3>();
+            let rr = va.rotate_lanes_right::<3>();
+            let mut al = a;
+            al.rotate_left(3 % n);
+            let mut ar = a;
+            ar.rotate_right(3 % n);
+            for i in 0..n {
+                assert_eq!(l[i], al[i]);
+                assert_eq!(rr[i], ar[i]);
+            }
+            assert_eq!(va.rotate_lanes_left::<0>().to_array(), a);
+            assert_eq!(va.rotate_lanes_left::<$lanes>().to_array(), a);
+
+            let c = va.broadcast::<1>();
+            for i in 0..n {
+                assert_eq!(c[i], a[1]);
+            }
+
+            let (lo, hi) = va.interleave(vb);
+            let zipped: Vec<$elem> = a.iter().zip(b.iter()).flat_map(|(&x, &y)| vec![x, y]).collect();
+            for i in 0..n {
+                assert_eq!(lo[i], zipped[i]);
+                assert_eq!(hi[i], zipped[n + i]);
+            }
+
+            let (evens, odds) = lo.deinterleave(hi);
+            for i in 0..n {
+                assert_eq!(evens[i], a[i]);
+                assert_eq!(odds[i], b[i]);
+            }
+        })*
+    }
+}
+
+permute_tests! {
+    permute_f32x2, f32x2, f32, 2;
+    permute_f32x4, f32x4, f32, 4;
+    permute_f64x4, f64x4, f64, 4;
+    permute_i32x8, i32x8, i32, 8;
+    permute_u16x16, u16x16, u16, 16;
+    permute_i8x32, i8x32, i8, 32;
+    permute_u8x64, u8x64, u8, 64;
+    permute_f32x16, f32x16, f32, 16;
+}
+
+#[test]
+fn complex_multiply() {
+    // (1 + 2i)(3 + 4i) = -5 + 10i, (5 + 6i)(7 + 8i) = -13 + 82i
+    let x = f32x4::new(1.0, 2.0, 5.0, 6.0);
+    let y = f32x4::new(3.0, 4.0, 7.0, 8.0);
+    let (xr, xi) = x.deinterleave(x);
+    let (yr, yi) = y.deinterleave(y);
+    let (re, im) = (xr * yr - xi * yi, xr * yi + xi * yr);
+    let (z, _) = re.interleave(im);
+    assert_eq!(z.to_array(), [-5.0, 10.0, -13.0, 82.0]);
+}
+
+fn swap_channels<V: SimdVector>(left: V, right: V) -> (V, V) {
+    let (lo, hi) = left.interleave(right);
+    let (l, r) = lo.rotate_lanes_left::<1>().deinterleave(hi.rotate_lanes_left::<1>());
+    (l.reverse().reverse(), r.broadcast::<0>().max(r))
+}
+
+#[test]
+fn permutations_through_trait() {
+    let (l, r) = swap_channels(i16x8::splat(1), i16x8::splat(2));
+    assert_eq!(l.to_array(), [2; 8]);
+    assert_eq!(r.to_array(), [1; 8]);
+}
@@ -0,0 +1,56 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn float_reductions() {
+    let v = f32x8::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+    assert_eq!(v.sum(), 36.0);
+    assert_eq!(v.product(), 40320.0);
+    assert_eq!(v.min_element(), 1.0);
+    assert_eq!(v.max_element(), 8.0);
+    assert_eq!(v.ordered_sum(), 36.0);
+
+    let w = f64x4::new(-1.5, 7.25, -9.0, 0.5);
+    assert_eq!(w.min_element(), -9.0);
+    assert_eq!(w.max_element(), 7.25);
+}
+
+#[test]
+fn float_sum_order() {
+    // 1e8 + 1 is rounded back to 1e8 in single precision
+    let v = f32x4::new(1.0e8, 1.0, -1.0e8, 1.0);
+    assert_eq!(v.sum(), (1.0e8 + -1.0e8) + (1.0 + 1.0));
+    assert_eq!(v.ordered_sum(), ((1.0e8 + 1.0) + -1.0e8) + 1.0);
+    assert_eq!(v.sum(), 2.0);
+    assert_eq!(v.ordered_sum(), 1.0);
+
+    let values = [0.1, 0.7, 1.3, -2.9, 3.3, 1e-3, 5.5, -0.25];
+    let v = f64x4::load(&values, 0);
+    assert_eq!(v.ordered_sum(), values[..4].iter().fold(-0.0, |a, b| a + b));
+    let v = f32x8::new(0.1, 0.7, 1.3, -2.9, 3.3, 1e-3, 5.5, -0.25);
+    let mut acc = v.extract(0);
+    for i in 1..8 {
+        acc += v.extract(i);
+    }
+    assert_eq!(v.ordered_sum(), acc);
+}
+
+#[test]
+fn integer_reductions() {
+    let v = u8x16::new(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+    assert_eq!(v.sum(), 136);
+    assert_eq!(v.min_element(), 1);
+    assert_eq!(v.max_element(), 16);
+    assert_eq!(v.and(), 0);
+    assert_eq!(v.or(), 31);
+    assert_eq!(v.xor(), 16);
+
+    let w = i32x4::new(-3, 5, -7, 2);
+    assert_eq!(w.sum(), -3);
+    assert_eq!(w.product(), 210);
+    assert_eq!(w.min_element(), -7);
+    assert_eq!(w.max_element(), 5);
+    assert_eq!(u64x4::new(0xff, 0xf0f, 0xfff, 0x1f0).and(), 0x0);
+    assert_eq!(u32x8::splat(0b1011).and(), 0b1011);
+    assert_eq!(i16x16::splat(1).replace(3, 3).xor(), 3 ^ 1);
+}
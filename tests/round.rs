@@ -0,0 +1,57 @@
+extern crate ssimd;
+use ssimd::*;
+
+const VALUES: [f32; 8] = [-2.5, -1.5, -0.7, -0.0, 0.5, 1.5, 2.3, 2.5];
+
+#[test]
+fn rounding() {
+    let x = f32x8::load(&VALUES, 0);
+    let y = f64x4::new(-2.5, -0.5, 0.5, 3.7);
+    for i in 0..8 {
+        let v = VALUES[i as usize];
+        assert_eq!(x.floor().extract(i), v.floor());
+        assert_eq!(x.ceil().extract(i), v.ceil());
+        assert_eq!(x.round().extract(i), v.round());
+        assert_eq!(x.round_ties_even().extract(i), v.round_ties_even());
+        assert_eq!(x.trunc().extract(i), v.trunc());
+        assert_eq!(x.fract().extract(i), v.fract());
+    }
+    assert_eq!(y.round().extract(0), -3.0);
+    assert_eq!(y.round_ties_even().extract(0), -2.0);
+    assert_eq!(y.round_ties_even().extract(2), 0.0);
+    assert_eq!(y.floor().extract(3), 3.0);
+    assert_eq!(y.ceil().extract(1), -0.0);
+    assert!(y.ceil().extract(1).is_sign_negative());
+    assert_eq!(f32x2::new(-1.25, 1.75).fract().extract(0), -0.25);
+}
+
+#[test]
+fn special_values() {
+    let x = f32x4::new(f32::NAN, f32::INFINITY, f32::NEG_INFINITY, -0.0);
+    assert!(x.floor().extract(0).is_nan());
+    assert_eq!(x.round().extract(1), f32::INFINITY);
+    assert_eq!(x.trunc().extract(2), f32::NEG_INFINITY);
+    assert!(x.round_ties_even().extract(3).is_sign_negative());
+    assert!(x.fract().extract(1).is_nan());
+}
+
+#[test]
+fn rounding_conversions() {
+    let x = f32x4::new(-1.5, -0.2, 0.5, 2.7);
+    assert!(x.to_i().eq(i32x4::new(-1, 0, 0, 2)).all());
+    assert!(x.to_i_round().eq(i32x4::new(-2, 0, 1, 3)).all());
+    assert!(x.to_i_floor().eq(i32x4::new(-2, -1, 0, 2)).all());
+    assert!(x.to_i_ceil().eq(i32x4::new(-1, 0, 1, 3)).all());
+    assert!(x.to_u_floor().eq(u32x4::new(0, 0, 0, 2)).all());
+    assert!(x.to_u_ceil().eq(u32x4::new(0, 0, 1, 3)).all());
+
+    let y = f64x2::new(-7.5, 7.5);
+    assert!(y.to_i().eq(i64x2::new(-7, 7)).all());
+    assert!(y.to_i_round().eq(i64x2::new(-8, 8)).all());
+    assert!(y.to_u_round().eq(u64x2::new(0, 8)).all());
+    assert!(f64x4::new(0.1, 1.9, -0.1, 3.0).to_i_ceil().eq(i64x4::new(1, 2, 0, 3)).all());
+
+    let z = f32x8::new(1.0e10, -1.0e10, f32::NAN, 0.49, 0.51, -0.49, -0.51, 2.5);
+    assert!(z.to_i_round().eq(i32x8::new(i32::MAX, i32::MIN, 0, 0, 1, 0, -1, 3)).all());
+    assert!(f32x2::new(0.5, 4.0e9).to_u_round().eq(u32x2::new(1, 4_000_000_000)).all());
+}
@@ -0,0 +1,51 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn operators_wrap() {
+    let a = u8x16::splat(250);
+    let b = u8x16::splat(10);
+    assert!((a + b).eq(u8x16::splat(4)).all());
+    assert!((b - a).eq(u8x16::splat(16)).all());
+    assert!((a * b).eq(u8x16::splat(196)).all());
+
+    let c = i16x16::splat(i16::MAX);
+    assert!((c + i16x16::splat(1)).eq(i16x16::splat(i16::MIN)).all());
+    assert_eq!(i32x4::new(i32::MAX, 1, 1, 1).sum(), i32::MIN + 2);
+    assert_eq!(u64x2::new(u64::MAX, 3).product(), u64::MAX - 2);
+    assert_eq!(u32x4::splat(u32::MAX).add_masked(u32x4::splat(2), bool32x4::new(true, false, true, false)).extract(0), 1);
+}
+
+#[test]
+fn wrapping() {
+    let a = i8x32::splat(i8::MIN);
+    assert!(a.wrapping_sub(i8x32::splat(1)).eq(i8x32::splat(i8::MAX)).all());
+    assert!(a.wrapping_mul(i8x32::splat(-1)).eq(a).all());
+    assert!(u16x8::splat(u16::MAX).wrapping_add(u16x8::splat(2)).eq(u16x8::splat(1)).all());
+}
+
+#[test]
+fn saturating() {
+    let a = u8x32::new(0, 1, 100, 200, 250, 255, 128, 127,
+                       0, 1, 100, 200, 250, 255, 128, 127,
+                       0, 1, 100, 200, 250, 255, 128, 127,
+                       0, 1, 100, 200, 250, 255, 128, 127);
+    let b = u8x32::splat(100);
+    for i in 0..32 {
+        assert_eq!(a.saturating_add(b).extract(i), a.extract(i).saturating_add(100));
+        assert_eq!(a.saturating_sub(b).extract(i), a.extract(i).saturating_sub(100));
+    }
+
+    let c = i16x16::new(i16::MIN, -30000, -1, 0, 1, 30000, i16::MAX, 5,
+                        i16::MIN, -30000, -1, 0, 1, 30000, i16::MAX, 5);
+    let d = i16x16::new(-10000, 10000, -10000, 10000, -10000, 10000, -10000, 10000,
+                        10000, -10000, 10000, -10000, 10000, -10000, 10000, -10000);
+    for i in 0..16 {
+        assert_eq!(c.saturating_add(d).extract(i), c.extract(i).saturating_add(d.extract(i)));
+        assert_eq!(c.saturating_sub(d).extract(i), c.extract(i).saturating_sub(d.extract(i)));
+    }
+
+    assert!(u32x8::splat(5).saturating_sub(u32x8::splat(6)).eq(u32x8::splat(0)).all());
+    assert!(i64x4::splat(i64::MAX).saturating_add(i64x4::splat(1)).eq(i64x4::splat(i64::MAX)).all());
+    assert!(i8x16::splat(-100).saturating_sub(i8x16::splat(100)).eq(i8x16::splat(i8::MIN)).all());
+}
@@ -0,0 +1,40 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn float_scalar_ops() {
+    let v = f32x4::new(1.0, -2.0, 0.5, 8.0);
+    assert!((v + 1.5).eq(v + f32x4::splat(1.5)).all());
+    assert!((1.5 - v).eq(f32x4::splat(1.5) - v).all());
+    assert!((v * 3.0).eq(v * f32x4::splat(3.0)).all());
+    assert!((2.0 / v).eq(f32x4::splat(2.0) / v).all());
+
+    let mut w = f64x4::new(1.0, 2.0, 3.0, 4.0);
+    w += 1.0;
+    w *= 2.0;
+    w -= 0.5;
+    w /= 0.5;
+    assert!(w.eq(f64x4::new(7.0, 11.0, 15.0, 19.0)).all());
+    assert!((0.1 * f64x2::splat(3.0)).eq(f64x2::splat(0.1 * 3.0)).all());
+}
+
+#[test]
+fn integer_scalar_ops() {
+    let v = u8x32::splat(200);
+    assert!((v + 100).eq(u8x32::splat(44)).all());
+    assert!((10 - v).eq(u8x32::splat(66)).all());
+    assert!((v % 7).eq(u8x32::splat(4)).all());
+    assert!((0xf0 & v).eq(u8x32::splat(0xc0)).all());
+    assert!((v | 1).eq(u8x32::splat(201)).all());
+    assert!((v ^ 0xff).eq(u8x32::splat(55)).all());
+
+    let mut w = i32x8::splat(-9);
+    w /= 2;
+    w *= -3;
+    w %= 5;
+    w &= 0b110;
+    w |= 1;
+    w ^= 8;
+    assert!(w.eq(i32x8::splat(11)).all());
+    assert!((100 / i16x8::new(1, 2, 3, 4, 5, 6, 7, 8)).eq(i16x8::new(100, 50, 33, 25, 20, 16, 14, 12)).all());
+}
@@ -0,0 +1,71 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn select_float_keeps_bits() {
+    let a = f32x4::new(-0.0, f32::NAN, 1.5, -3.0);
+    let b = f32x4::new(0.0, 2.0, f32::INFINITY, 7.0);
+    let mask = bool32x4::new(true, true, false, false);
+    let c = mask.select(a, b);
+    assert_eq!(c.extract(0).to_bits(), (-0.0f32).to_bits());
+    assert!(c.extract(1).is_nan());
+    assert_eq!(c.extract(2), f32::INFINITY);
+    assert_eq!(c.extract(3), 7.0);
+
+    let x = f64x4::new(-2.0, 0.25, 3.0, 0.5);
+    let clamped = x.lt(f64x4::splat(0.0)).select(f64x4::splat(0.0), x);
+    let clamped = clamped.gt(f64x4::splat(1.0)).select(f64x4::splat(1.0), clamped);
+    assert_eq!(clamped.extract(0), 0.0);
+    assert_eq!(clamped.extract(1), 0.25);
+    assert_eq!(clamped.extract(2), 1.0);
+    assert_eq!(clamped.extract(3), 0.5);
+}
+
+#[test]
+fn select_integer() {
+    let a = i8x32::splat(-128);
+    let b = i8x32::splat(127);
+    let c = bool8x32::splat(false).replace(5, true).select(a, b);
+    for i in 0..32 {
+        assert_eq!(c.extract(i), if i == 5 { -128 } else { 127 });
+    }
+    let d = bool64x2::new(false, true).select(u64x2::splat(!0), u64x2::splat(3));
+    assert_eq!(d.extract(0), 3);
+    assert_eq!(d.extract(1), !0);
+}
+
+#[test]
+fn masked_arithmetic() {
+    let a = f32x8::splat(6.0);
+    let b = f32x8::new(1.0, 2.0, 3.0, 0.0, 1.0, 2.0, 3.0, 0.0);
+    let mask = b.ne(f32x8::splat(0.0));
+    let c = a.div_masked(b, mask);
+    assert_eq!(c.extract(2), 2.0);
+    assert_eq!(c.extract(3), 6.0);
+    assert_eq!(a.add_masked(b, !mask).extract(0), 6.0);
+    assert_eq!(a.sub_masked(b, mask).extract(1), 4.0);
+    assert_eq!(a.mul_masked(b, mask).extract(6), 18.0);
+
+    // masked-off lanes never evaluate the operation
+    let x = u32x4::new(10, 10, 10, 10);
+    let y = u32x4::new(5, 0, 2, 0);
+    let z = x.div_masked(y, y.ne(u32x4::splat(0)));
+    assert_eq!(z.extract(0), 2);
+    assert_eq!(z.extract(1), 10);
+    let w = u8x16::splat(250).add_masked(u8x16::splat(10), bool8x16::splat(false));
+    assert_eq!(w.extract(0), 250);
+}
+
+// `select` is an inherent method of the boolean vectors, so it needs no
+// trait import for any element type
+mod no_import {
+    use ssimd::{bool32x4, f32x4, i32x4, u32x4};
+
+    #[test]
+    fn select_every_element_type() {
+        let mask = bool32x4::new(true, false, false, true);
+        assert_eq!(mask.select(f32x4::splat(1.0), f32x4::splat(2.0)).to_array(), [1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(mask.select(i32x4::splat(-1), i32x4::splat(2)).to_array(), [-1, 2, 2, -1]);
+        assert_eq!(mask.select(u32x4::splat(7), u32x4::splat(0)).to_array(), [7, 0, 0, 7]);
+    }
+}
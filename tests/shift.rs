@@ -0,0 +1,67 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn shift_by_scalar() {
+    let a = u8x16::splat(0b1001_0110);
+    assert!((a << 3).eq(u8x16::splat(0b1011_0000)).all());
+    assert!((a >> 3).eq(u8x16::splat(0b0001_0010)).all());
+    assert!((a << 8).eq(u8x16::splat(0)).all());
+    assert!((a >> 200).eq(u8x16::splat(0)).all());
+
+    let b = i32x4::new(-16, 16, i32::MIN, -1);
+    assert!((b >> 2).eq(i32x4::new(-4, 4, i32::MIN / 4, -1)).all());
+    assert!((b >> 32).eq(i32x4::new(-1, 0, -1, -1)).all());
+    assert!((b << 40).eq(i32x4::splat(0)).all());
+    assert!((i8x32::splat(1) << 7).eq(i8x32::splat(i8::MIN)).all());
+}
+
+#[test]
+fn shift_by_vector() {
+    let a = u32x8::splat(0x8000_0001);
+    let n = u32x8::new(0, 1, 4, 31, 32, 33, 100, u32::MAX);
+    let l = a << n;
+    let r = a >> n;
+    for i in 0..8 {
+        let count = n.extract(i);
+        assert_eq!(l.extract(i), a.extract(i).checked_shl(count).unwrap_or(0));
+        assert_eq!(r.extract(i), a.extract(i).checked_shr(count).unwrap_or(0));
+    }
+
+    let b = i16x8::new(-256, -256, -256, 256, 256, 7, -7, i16::MIN);
+    let m = i16x8::new(1, 8, 16, 1, 16, -1, -1, 15);
+    let s = b >> m;
+    assert!(s.eq(i16x8::new(-128, -1, -1, 128, 0, 0, -1, -1)).all());
+    assert!((b << m).eq(i16x8::new(-512, 0, 0, 512, 0, 0, 0, 0)).all());
+
+    let mut c = u64x2::new(1, 1);
+    c <<= u64x2::new(63, 1 << 32);
+    assert!(c.eq(u64x2::new(1 << 63, 0)).all());
+    c >>= u64x2::new(62, 0);
+    assert!(c.eq(u64x2::new(2, 0)).all());
+}
+
+#[test]
+fn wrapping_shifts() {
+    let a = u16x16::splat(3);
+    assert!(a.wrapping_shl(17).eq(u16x16::splat(6)).all());
+    assert!(a.wrapping_shr(16).eq(a).all());
+    assert!(i64x4::splat(-8).wrapping_shr(65).eq(i64x4::splat(-4)).all());
+}
+
+#[test]
+fn rotates() {
+    let a = u32x4::splat(0x8000_0001);
+    assert!(a.rotate_left(1).eq(u32x4::splat(3)).all());
+    assert!(a.rotate_right(33).eq(u32x4::splat(0xc000_0000)).all());
+
+    let n = u32x4::new(0, 4, 16, 36);
+    let l = a.rotate_left_by(n);
+    let r = a.rotate_right_by(n);
+    for i in 0..4 {
+        assert_eq!(l.extract(i), 0x8000_0001u32.rotate_left(n.extract(i)));
+        assert_eq!(r.extract(i), 0x8000_0001u32.rotate_right(n.extract(i)));
+    }
+    assert!(i8x16::splat(-128).rotate_left(1).eq(i8x16::splat(1)).all());
+    assert!(u8x32::splat(1).rotate_right_by(u8x32::splat(1)).eq(u8x32::splat(128)).all());
+}
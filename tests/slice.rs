@@ -0,0 +1,50 @@
+extern crate ssimd;
+use ssimd::*;
+use std::mem::align_of;
+
+#[test]
+fn slice_from_splits_head_and_tail() {
+    let data: Vec<f32> = (0..103).map(|i| i as f32).collect();
+    for offset in 0..8 {
+        let s = &data[offset..];
+        let (head, vectors, tail) = f32x4::slice_from(s);
+        // `align_to` may put any number of elements in the head, so only
+        // check that the three parts are the input in order, not copies
+        let flat = f32x4::as_flattened(vectors);
+        assert_eq!([head, flat, tail].concat(), s);
+        assert_eq!(head.as_ptr(), s.as_ptr());
+        assert_eq!(flat.as_ptr(), s[head.len()..].as_ptr());
+        if !vectors.is_empty() {
+            assert_eq!(vectors.as_ptr() as usize % align_of::<f32x4>(), 0);
+        }
+    }
+}
+
+#[test]
+fn slice_from_mut_writes_in_place() {
+    let mut data = [1u8; 200];
+    let (head_len, lanes) = {
+        let (head, vectors, tail) = u8x32::slice_from_mut(&mut data[3..]);
+        for x in head.iter_mut().chain(tail.iter_mut()) {
+            *x = 2;
+        }
+        for v in vectors.iter_mut() {
+            *v = v.saturating_add(u8x32::splat(254));
+        }
+        (head.len(), vectors.len() * 32)
+    };
+    assert_eq!(data[..3], [1, 1, 1]);
+    for (i, &x) in data[3..].iter().enumerate() {
+        let in_vectors = i >= head_len && i < head_len + lanes;
+        assert_eq!(x, if in_vectors { 255 } else { 2 });
+    }
+}
+
+#[test]
+fn flatten_vectors() {
+    let mut vectors = [i64x2::new(1, 2), i64x2::new(3, 4), i64x2::new(5, 6)];
+    assert_eq!(i64x2::as_flattened(&vectors), &[1, 2, 3, 4, 5, 6]);
+    i64x2::as_flattened_mut(&mut vectors)[3] = -4;
+    assert_eq!(vectors[1].extract(1), -4);
+    assert!(f64x8::as_flattened(&[]).is_empty());
+}
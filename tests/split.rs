@@ -0,0 +1,64 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn split_256_bit() {
+    let v = f32x8::new(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0);
+    let (lo, hi): (f32x4, f32x4) = v.split();
+    assert_eq!(lo.to_array(), [0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(hi.to_array(), [4.0, 5.0, 6.0, 7.0]);
+    assert!(v.low().eq(lo).all() && v.high().eq(hi).all());
+    assert_eq!(f32x8::from_halves(lo, hi).to_array(), v.to_array());
+
+    let b: [u8; 32] = std::array::from_fn(|i| i as u8);
+    let (lo, hi): (u8x16, u8x16) = u8x32::from(b).split();
+    assert_eq!(lo.extract(15), 15);
+    assert_eq!(hi.extract(0), 16);
+    assert_eq!(u8x32::from_halves(hi, lo).extract(0), 16);
+
+    let (lo, hi): (i64x2, i64x2) = i64x4::new(1, 2, 3, 4).split();
+    assert_eq!((lo + hi).to_array(), [4, 6]);
+    let (lo, hi): (u16x8, i16x8) = (u16x16::splat(1).low(), i16x16::splat(-1).high());
+    assert_eq!((lo.sum(), hi.sum()), (8, -8));
+}
+
+#[test]
+fn split_512_bit() {
+    let v = f64x8::new(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0);
+    let (lo, hi): (f64x4, f64x4) = v.split();
+    assert_eq!(hi.to_array(), [4.0, 5.0, 6.0, 7.0]);
+    assert_eq!(f64x8::from_halves(lo, hi).to_array(), v.to_array());
+
+    let w: i8x64 = i8x64::from_halves(i8x32::splat(-1), i8x32::splat(1));
+    assert_eq!(w.low().sum(), -32);
+    assert_eq!(w.high().sum(), 32);
+    let (lo, _): (u32x8, u32x8) = u32x16::splat(3).split();
+    assert_eq!(lo.sum(), 24);
+}
+
+#[test]
+fn split_masks() {
+    let m = bool32x8::new(true, true, true, true, false, false, false, false);
+    let (lo, hi): (bool32x4, bool32x4) = m.split();
+    assert!(lo.all() && !hi.any());
+    let n: bool32x8 = bool32x8::from_halves(hi, lo);
+    assert!(!n.extract(0) && n.extract(7));
+    assert!(bool8x64::from_halves(bool8x32::splat(true), bool8x32::splat(true)).all());
+    assert!(bool16x16::splat(true).high().all());
+}
+
+// A reduction written by hand with halving adds
+fn sum8(v: f32x8) -> f32 {
+    let (lo, hi) = v.split();
+    let v = lo + hi;
+    let (lo, hi) = v.split();
+    let v = lo + hi;
+    v.extract(0) + v.extract(1)
+}
+
+#[test]
+fn halving_reduction() {
+    let v = f32x8::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+    assert_eq!(sum8(v), 36.0);
+    assert_eq!(sum8(v), v.sum());
+}
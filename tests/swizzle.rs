@@ -0,0 +1,50 @@
+extern crate ssimd;
+use ssimd::*;
+
+#[test]
+fn swizzle_one_vector() {
+    let v = f32x4::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(swizzle!(v, [3, 2, 1, 0]).to_array(), [4.0, 3.0, 2.0, 1.0]);
+    assert_eq!(swizzle!(v, [1, 1, 1, 1]).to_array(), [2.0; 4]);
+    assert_eq!(swizzle!(v, [2, 3]).to_array(), [3.0, 4.0]);
+
+    let w = u8x16::new(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    let doubled: u8x32 = swizzle!(w, [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7,
+                                      8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13, 14, 14, 15, 15]);
+    assert_eq!(doubled.extract(31), 15);
+    assert_eq!(doubled.extract(6), 3);
+}
+
+#[test]
+fn shuffle_two_vectors() {
+    let a = i32x4::new(0, 1, 2, 3);
+    let b = i32x4::new(4, 5, 6, 7);
+    assert_eq!(shuffle!(a, b, [0, 4, 1, 5]).to_array(), [0, 4, 1, 5]);
+    assert_eq!(shuffle!(a, b, [7, 6, 5, 4]).to_array(), [7, 6, 5, 4]);
+    let c: i32x8 = shuffle!(a, b, [0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(c.sum(), 28);
+
+    let m = bool64x2::new(true, false);
+    let n = bool64x2::new(false, true);
+    assert!(shuffle!(m, n, [0, 3]).all());
+    assert!(!swizzle!(m, [1, 1]).any());
+}
+
+struct Reverse8;
+impl Shuffle<8> for Reverse8 {
+    const INDEX: [usize; 8] = [7, 6, 5, 4, 3, 2, 1, 0];
+}
+
+fn reverse8<T: SimdElement>(v: Simd<T, 8>) -> Simd<T, 8> {
+    v.swizzle::<Reverse8, 8>()
+}
+
+#[test]
+fn shuffle_trait() {
+    let v = u16x8::new(1, 2, 3, 4, 5, 6, 7, 8);
+    assert_eq!(reverse8(v).to_array(), [8, 7, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(reverse8(f64x8::splat(0.5).replace(0, 1.0)).extract(7), 1.0);
+    // indices below 8 pick from the first vector
+    let w = v.shuffle::<Reverse8, 8>(u16x8::splat(0));
+    assert_eq!(w.to_array(), [8, 7, 6, 5, 4, 3, 2, 1]);
+}
@@ -0,0 +1,67 @@
+extern crate ssimd;
+use ssimd::*;
+
+fn axpy<V: SimdFloat<Elem = f32>>(z: &mut [f32], a: f32, x: &[f32], y: &[f32]) {
+    let mut i = 0;
+    while i + V::LANES <= z.len() {
+        V::splat(a).mul_add(V::load(x, i), V::load(y, i)).store(z, i);
+        i += V::LANES;
+    }
+}
+
+fn relu<V: SimdVector>(v: V) -> V {
+    let zero = V::splat(Default::default());
+    v.lt(zero).select(zero, v)
+}
+
+fn popcount_lanes<M: SimdMask>(m: M) -> usize {
+    (0..M::LANES as u32).filter(|&i| m.extract(i)).count()
+}
+
+fn mix<V: SimdInt>(a: V, b: V) -> V {
+    (a ^ b).rotate_left(3).wrapping_add(a & b) >> 1
+}
+
+#[test]
+fn float_kernel_any_width() {
+    let x: Vec<f32> = (0..16).map(|i| i as f32).collect();
+    let y = vec![1.0f32; 16];
+    let mut z4 = vec![0.0; 16];
+    let mut z8 = vec![0.0; 16];
+    let mut z16 = vec![0.0; 16];
+    axpy::<f32x4>(&mut z4, 2.0, &x, &y);
+    axpy::<f32x8>(&mut z8, 2.0, &x, &y);
+    axpy::<f32x16>(&mut z16, 2.0, &x, &y);
+    for (i, z) in z4.iter().enumerate() {
+        assert_eq!(*z, 2.0 * i as f32 + 1.0);
+    }
+    assert_eq!(z4, z8);
+    assert_eq!(z4, z16);
+
+    assert_eq!(f64x4::LANES, 4);
+    assert_eq!(<f64x8 as SimdVector>::splat(0.0).exp().sum(), 8.0);
+}
+
+#[test]
+fn vector_and_mask_traits() {
+    let r = relu(i16x8::new(-1, 2, -3, 4, -5, 6, -7, 8));
+    assert_eq!(r.sum(), 20);
+    assert!(relu(f32x4::new(-0.5, 0.5, -2.0, 2.0)).eq(f32x4::new(0.0, 0.5, 0.0, 2.0)).all());
+
+    assert_eq!(popcount_lanes(u8x32::splat(3).gt(u8x32::splat(2))), 32);
+    assert_eq!(popcount_lanes(bool64x2::new(true, false)), 1);
+    assert_eq!(<bool32x4 as SimdMask>::splat(true).to_i().extract(0), -1);
+}
+
+#[test]
+fn int_kernel_any_width() {
+    let a = u32x4::new(1, 2, 3, 0xffff_ffff);
+    let b = u32x4::splat(6);
+    let c = mix(a, b);
+    for i in 0..4 {
+        let (x, y) = (a.extract(i), b.extract(i));
+        assert_eq!(c.extract(i), (x ^ y).rotate_left(3).wrapping_add(x & y) >> 1);
+    }
+    let d = mix(i8x64::splat(-7), i8x64::splat(5));
+    assert_eq!(d.extract(63), ((-7i8 ^ 5).rotate_left(3).wrapping_add(-7 & 5)) >> 1);
+}
@@ -0,0 +1,47 @@
+extern crate ssimd;
+use ssimd::*;
+
+macro_rules! transpose_tests {
+    ($($test: ident, $transpose: ident, $name: ident, $elem: ident, $n: expr;)*) => {
+        $(#[test]
+        fn $test() {
+            let rows: [[$elem; $n]; $n] = std::array::from_fn(|i| std::array::from_fn(|j| (i * $n + j) as $elem));
+            let mut m = rows.map($name::from);
+            $transpose(&mut m);
+            for i in 0..$n {
+                for j in 0..$n {
+                    assert_eq!(m[j][i], rows[i][j]);
+                }
+            }
+            // transposing twice gives back the original matrix
+            $transpose(&mut m);
+            assert_eq!(m.map(|r| r.to_array()), rows);
+        })*
+    }
+}
+
+transpose_tests! {
+    transpose_f32x4, transpose4, f32x4, f32, 4;
+    transpose_f64x4, transpose4, f64x4, f64, 4;
+    transpose_f64x4_named, transpose4_f64, f64x4, f64, 4;
+    transpose_i32x4, transpose4, i32x4, i32, 4;
+    transpose_u64x4, transpose4, u64x4, u64, 4;
+    transpose_f32x8, transpose8, f32x8, f32, 8;
+    transpose_f64x8, transpose8, f64x8, f64, 8;
+    transpose_u16x8, transpose8, u16x8, u16, 8;
+    transpose_i32x8, transpose8, i32x8, i32, 8;
+}
+
+#[test]
+fn aos_to_soa() {
+    // four xyzw points, one per vector, become one vector per coordinate
+    let mut m = [f32x4::new(1.0, 2.0, 3.0, 1.0),
+                 f32x4::new(4.0, 5.0, 6.0, 1.0),
+                 f32x4::new(7.0, 8.0, 9.0, 1.0),
+                 f32x4::new(10.0, 11.0, 12.0, 1.0)];
+    transpose4(&mut m);
+    let [x, y, z, w] = m;
+    assert_eq!(x.to_array(), [1.0, 4.0, 7.0, 10.0]);
+    assert_eq!((x * x + y * y + z * z).extract(0), 14.0);
+    assert_eq!(w.to_array(), [1.0; 4]);
+}